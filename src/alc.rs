@@ -1,4 +1,4 @@
-use std::{borrow::{Borrow, BorrowMut}, ops::{Deref, DerefMut}, sync::Arc};
+use std::{borrow::{Borrow, BorrowMut, Cow}, collections::HashSet, hash::{Hash, Hasher}, mem, ops::{Deref, DerefMut}, sync::Arc};
 
 use crate::lazy::LazyClone;
 
@@ -7,6 +7,12 @@ use crate::lazy::LazyClone;
 /// that doesn't implement `LazyClone` and need thread-safety
 /// 
 /// If you're able to implement LazyClone for the data that you need, do it, if not, use this wrapper
+/// 
+/// # Copy-on-write through `DerefMut`
+/// 
+/// `Alc` implements `DerefMut` for ergonomics, so calling a `&mut self` method of the value (like `push` on a `Alc<Vec<_>>`) 
+/// works directly. But it goes through `read_mut`, which clones the whole value first if it's shared. 
+/// Use `cow_deref_mut` to make that explicit
 pub struct Alc<T: Clone> (Arc<T>);
 
 impl<T: Clone> Alc<T> {
@@ -16,6 +22,14 @@ impl<T: Clone> Alc<T> {
         Self(Arc::new(value))
     }
 
+    #[inline(always)]
+    /// Creates a new Alc from a clone of the referenced value
+    /// 
+    /// The value is cloned once, so this may be expensive
+    pub fn from_ref(value: &T) -> Self {
+        Self::new(value.clone())
+    }
+
     #[inline(always)]
     /// Takes a reference of the lazy cloned value
     pub fn read(&self) -> &T {
@@ -34,6 +48,48 @@ impl<T: Clone> Alc<T> {
         Arc::get_mut(&mut self.0).unwrap()
     }
 
+    #[inline(always)]
+    /// Runs `f` with a reference to the lazy cloned value and returns its result
+    /// 
+    /// This never clones the value, even if it's shared
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        f(self.read())
+    }
+
+    #[inline(always)]
+    /// Runs `f` with a mutable reference to the lazy cloned value and returns its result
+    /// 
+    /// Just like `read_mut`, the value is cloned first if it's shared
+    pub fn with_mut<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> R {
+        f(self.read_mut())
+    }
+
+    #[inline(always)]
+    /// Same as `with_mut`, named after `Lc::scoped_mut`
+    /// 
+    /// The value is cloned first if it's shared, so the other clones are never affected
+    pub fn scoped_mut<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> R {
+        self.with_mut(f)
+    }
+
+    #[inline(always)]
+    /// Returns a guard with exclusive access to the value, that can be used as a `&mut T`
+    /// 
+    /// Just like `read_mut`, the value is cloned when the guard is created if it's shared, 
+    /// so the other clones are never affected
+    pub fn write_guard(&mut self) -> AlcWriteGuard<'_, T> {
+        AlcWriteGuard { value: self.read_mut() }
+    }
+
+    #[inline(always)]
+    #[must_use]
+    /// Explicitly named version of `DerefMut`, it's the same as `read_mut`
+    /// 
+    /// The value is cloned first if it's shared, so this may be expensive
+    pub fn cow_deref_mut(&mut self) -> &mut T {
+        self.read_mut()
+    }
+
     #[inline(always)]
     /// Replaces the cloned value by another
     /// 
@@ -42,6 +98,15 @@ impl<T: Clone> Alc<T> {
         self.0 = Arc::new(value);
     }
 
+    #[inline(always)]
+    /// Replaces the cloned value by another and returns the old one
+    /// 
+    /// Does not affect any value lazily cloned from this value. 
+    /// If the old value was shared, it's cloned out, otherwise it's moved out in O(1)
+    pub fn replace(&mut self, value: T) -> T {
+        mem::replace(self, Alc::new(value)).unwrap()
+    }
+
     #[inline(always)]
     /// Does an actual clone of the contained value
     /// 
@@ -71,6 +136,29 @@ impl<T: Clone> Alc<T> {
             .expect("Destroyed a lazy clone that was being shared, this is invalid.")
     }
 
+    #[inline(always)]
+    /// Safely unwraps the lazy clone and returns the inner data in O(1) if this is the last handle to it
+    /// 
+    /// Returns `None` if the data is still shared, in which case this handle is just dropped
+    /// 
+    /// Unlike checking `is_mutable` before calling `destroy`, this is safe even when other threads are dropping their handles
+    pub fn into_inner(self) -> Option<T> {
+        Arc::into_inner(self.0)
+    }
+
+    #[inline(always)]
+    /// Borrows the value as a `Cow`, without cloning it
+    pub fn as_cow(&self) -> Cow<'_, T> {
+        Cow::Borrowed(self.read())
+    }
+
+    #[inline(always)]
+    /// Converts the Alc into an owned `Cow`, the value is only cloned if it's shared (see `unwrap`)
+    pub fn into_cow(self) -> Cow<'static, T>
+    where T: 'static {
+        Cow::Owned(self.unwrap())
+    }
+
     /// Unwraps the lazy clone and returns the inner data in O(1) if the Alc is mutable, otherwise performs a clone.
     pub fn unwrap(self) -> T {
         if self.is_mutable() { 
@@ -81,6 +169,28 @@ impl<T: Clone> Alc<T> {
     }
 }
 
+#[derive(Debug)]
+/// Exclusive access to the value of an `Alc`, produced by `Alc::write_guard`
+pub struct AlcWriteGuard<'a, T: Clone> {
+    value: &'a mut T,
+}
+
+impl<T: Clone> Deref for AlcWriteGuard<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.value
+    }
+}
+
+impl<T: Clone> DerefMut for AlcWriteGuard<'_, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.value
+    }
+}
+
 impl<T: Clone> LazyClone for Alc<T> {
     #[inline(always)]
     fn lazy(&self) -> Self {
@@ -96,6 +206,13 @@ impl<T: Clone> LazyClone for Alc<T> {
     fn is_mutable(&self) -> bool {
         Arc::strong_count(&self.0) == 1
     }    
+
+    #[inline(always)]
+    /// Returns `true` while the Alc is unique and `false` once it's shared, 
+    /// since writing to a shared value always pays a full clone of it
+    fn clone_is_cheap(&self) -> bool {
+        self.is_mutable()
+    }
 }
 
 impl<T: Clone> Clone for Alc<T> {
@@ -105,6 +222,24 @@ impl<T: Clone> Clone for Alc<T> {
     }
 }
 
+impl<T: Clone + PartialEq> PartialEq for Alc<T> {
+    /// Compares the values, not the pointers. Use `ptr_eq` for that
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.read() == other.read()
+    }
+}
+
+impl<T: Clone + Eq> Eq for Alc<T> {}
+
+impl<T: Clone + Hash> Hash for Alc<T> {
+    /// Hashes the value, so it's consistent with the value's `Borrow` implementations
+    #[inline(always)]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.read().hash(state)
+    }
+}
+
 impl<T: Clone> From<T> for Alc<T> {
     #[inline(always)]
     fn from(value: T) -> Self {
@@ -122,6 +257,7 @@ impl<T: Clone> Deref for Alc<T> {
 }
 
 impl<T: Clone> DerefMut for Alc<T> {
+    /// Same as `read_mut`, so the value is cloned first if it's shared
     #[inline(always)]
     fn deref_mut(&mut self) -> &mut Self::Target {
         self.read_mut()
@@ -135,9 +271,436 @@ impl<T: Clone> Borrow<T> for Alc<T> {
     }
 }
 
+impl Borrow<str> for Alc<String> {
+    #[inline(always)]
+    fn borrow(&self) -> &str {
+        self.read()
+    }
+}
+
 impl<T: Clone> BorrowMut<T> for Alc<T> {
     #[inline(always)]
     fn borrow_mut(&mut self) -> &mut T {
         self.read_mut()
     }
+}
+
+/// Removes the handles that point to the same allocation as an earlier handle of `handles`
+/// 
+/// Handles are compared with `Alc::ptr_eq`, so distinct allocations holding equal values are all kept. 
+/// The first occurrence of each allocation is kept and the order is preserved
+pub fn dedup_handles<T: Clone>(handles: &mut Vec<Alc<T>>) {
+    let mut seen = HashSet::new();
+    handles.retain(|handle| seen.insert(Arc::as_ptr(&handle.0)));
+}
+
+#[derive(Debug)]
+/// AlcSlice is a thread-safe lazy clone of a slice, stored in a single `Arc<[T]>` allocation
+/// 
+/// It has no per-element overhead, so it's the cheapest way to share immutable slices. 
+/// Mutating it through `make_mut` copies the whole slice if it's shared, `with_owned` materializes a `Vec` to change its length
+pub struct AlcSlice<T: Clone> (Arc<[T]>);
+
+impl<T: Clone> AlcSlice<T> {
+    #[inline(always)]
+    /// Creates a new AlcSlice from a vector
+    pub fn new(value: Vec<T>) -> Self {
+        Self(Arc::from(value))
+    }
+
+    #[inline(always)]
+    /// Takes a reference of the lazy cloned slice
+    pub fn read(&self) -> &[T] {
+        &self.0
+    }
+
+    /// Ensures that the lazily cloned slice is mutable and returns a mutable reference to it
+    /// 
+    /// If the slice is shared, it's copied into a new allocation first. 
+    /// The length can't be changed through it, use `with_owned` for that
+    pub fn make_mut(&mut self) -> &mut [T] {
+        if !self.is_mutable() {
+            *self = self.eager();
+        }
+
+        Arc::get_mut(&mut self.0).unwrap()
+    }
+
+    /// Materializes the slice into an owned `Vec`, runs `f` on it and stores the result back
+    /// 
+    /// The elements are always cloned into the `Vec` and then moved into a new allocation, 
+    /// so this is O(n) even if the slice isn't shared. The other clones aren't affected
+    pub fn with_owned<R>(&mut self, f: impl FnOnce(&mut Vec<T>) -> R) -> R {
+        let mut vec = self.read().to_vec();
+        let result = f(&mut vec);
+        self.0 = Arc::from(vec);
+
+        result
+    }
+
+    #[inline(always)]
+    /// Clones the elements into an owned `Vec`
+    pub fn into_owned(self) -> Vec<T> {
+        self.read().to_vec()
+    }
+
+    #[inline(always)]
+    /// Checks if two AlcSlice are pointing to the same data
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<T: Clone> LazyClone for AlcSlice<T> {
+    #[inline(always)]
+    fn lazy(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+
+    #[inline(always)]
+    fn eager(&self) -> Self {
+        Self(Arc::from(self.read()))
+    }
+
+    #[inline(always)]
+    fn is_mutable(&self) -> bool {
+        Arc::strong_count(&self.0) == 1
+    }
+}
+
+impl<T: Clone> Clone for AlcSlice<T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        LazyClone::lazy(self)
+    }
+}
+
+impl<T: Clone> From<Vec<T>> for AlcSlice<T> {
+    #[inline(always)]
+    fn from(value: Vec<T>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: Clone> From<&[T]> for AlcSlice<T> {
+    #[inline(always)]
+    fn from(value: &[T]) -> Self {
+        Self(Arc::from(value))
+    }
+}
+
+impl<T: Clone> Deref for AlcSlice<T> {
+    type Target = [T];
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.read()
+    }
+}
+
+#[derive(Debug)]
+/// AlcStr is a thread-safe lazy clone of a string, stored in a single `Arc<str>` allocation
+/// 
+/// Mutating it through `make_mut` copies the whole string if it's shared, `with_owned` materializes a `String` to change its length
+pub struct AlcStr (Arc<str>);
+
+impl AlcStr {
+    #[inline(always)]
+    /// Creates a new AlcStr from a string
+    pub fn new(value: String) -> Self {
+        Self(Arc::from(value))
+    }
+
+    #[inline(always)]
+    /// Takes a reference of the lazy cloned string
+    pub fn read(&self) -> &str {
+        &self.0
+    }
+
+    /// Ensures that the lazily cloned string is mutable and returns a mutable reference to it
+    /// 
+    /// If the string is shared, it's copied into a new allocation first. 
+    /// The length can't be changed through it, use `with_owned` for that
+    pub fn make_mut(&mut self) -> &mut str {
+        if !self.is_mutable() {
+            *self = self.eager();
+        }
+
+        Arc::get_mut(&mut self.0).unwrap()
+    }
+
+    /// Materializes the string into an owned `String`, runs `f` on it and stores the result back
+    /// 
+    /// The contents are always copied into the `String` and then moved into a new allocation, 
+    /// so this is O(n) even if the string isn't shared. The other clones aren't affected
+    pub fn with_owned<R>(&mut self, f: impl FnOnce(&mut String) -> R) -> R {
+        let mut string = String::from(self.read());
+        let result = f(&mut string);
+        self.0 = Arc::from(string);
+
+        result
+    }
+
+    #[inline(always)]
+    /// Copies the contents into an owned `String`
+    pub fn into_owned(self) -> String {
+        String::from(self.read())
+    }
+
+    #[inline(always)]
+    /// Checks if two AlcStr are pointing to the same data
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl LazyClone for AlcStr {
+    #[inline(always)]
+    fn lazy(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+
+    #[inline(always)]
+    fn eager(&self) -> Self {
+        Self(Arc::from(self.read()))
+    }
+
+    #[inline(always)]
+    fn is_mutable(&self) -> bool {
+        Arc::strong_count(&self.0) == 1
+    }
+}
+
+impl Clone for AlcStr {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        LazyClone::lazy(self)
+    }
+}
+
+impl From<String> for AlcStr {
+    #[inline(always)]
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<&str> for AlcStr {
+    #[inline(always)]
+    fn from(value: &str) -> Self {
+        Self(Arc::from(value))
+    }
+}
+
+impl Deref for AlcStr {
+    type Target = str;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.read()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{borrow::Cow, collections::HashSet};
+
+    use crate::lazy::LazyClone;
+
+    use super::{Alc, AlcSlice, AlcStr};
+
+    #[test]
+    fn into_inner() {
+        let unique = Alc::new(String::from("unique"));
+        assert_eq!(unique.into_inner(), Some(String::from("unique")));
+
+        let shared = Alc::new(String::from("shared"));
+        let other = shared.lazy();
+
+        assert_eq!(shared.into_inner(), None);
+        assert_eq!(other.into_inner(), Some(String::from("shared")));
+    }
+
+    #[test]
+    fn slice() {
+        let mut slice = AlcSlice::from(vec![1, 2, 3]);
+        let other = slice.lazy();
+
+        assert!(slice.ptr_eq(&other));
+        assert!(!slice.is_mutable());
+
+        slice.make_mut()[0] = 10;
+
+        assert!(!slice.ptr_eq(&other));
+        assert!(slice.is_mutable());
+        assert_eq!(slice.read(), &[10, 2, 3]);
+        assert_eq!(other.read(), &[1, 2, 3]);
+
+        let len = slice.with_owned(|vec| {
+            vec.push(4);
+            vec.len()
+        });
+
+        assert_eq!(len, 4);
+        assert_eq!(slice.read(), &[10, 2, 3, 4]);
+        assert_eq!(other.into_owned(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn str() {
+        let mut string = AlcStr::from("hello");
+        let other = string.lazy();
+
+        assert!(string.ptr_eq(&other));
+
+        string.make_mut().make_ascii_uppercase();
+
+        assert!(!string.ptr_eq(&other));
+        assert_eq!(string.read(), "HELLO");
+        assert_eq!(other.read(), "hello");
+
+        let lazy = string.lazy();
+        string.with_owned(|owned| owned.push_str(", world"));
+
+        assert_eq!(string.read(), "HELLO, world");
+        assert_eq!(lazy.into_owned(), "HELLO");
+    }
+
+    #[test]
+    fn with() {
+        let mut alc = Alc::new(vec![1, 2, 3]);
+        let other = alc.lazy();
+
+        assert_eq!(alc.with(|vec| vec.iter().sum::<i32>()), 6);
+        assert!(alc.ptr_eq(&other));
+
+        assert_eq!(alc.with_mut(|vec| { vec.push(4); vec.len() }), 4);
+        assert!(!alc.ptr_eq(&other));
+        assert_eq!(other.read(), &vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn write_guard() {
+        let mut alc = Alc::new(String::from("hello"));
+        let other = alc.lazy();
+
+        {
+            let mut guard = alc.write_guard();
+            guard.push_str(", world");
+            guard.make_ascii_uppercase();
+        }
+
+        assert_eq!(alc.read(), "HELLO, WORLD");
+        assert_eq!(other.read(), "hello");
+        assert!(alc.is_mutable());
+        assert!(other.is_mutable());
+    }
+
+    #[test]
+    fn clone_is_cheap() {
+        let value = Alc::new(vec![1, 2, 3]);
+        assert!(value.clone_is_cheap());
+
+        let other = value.lazy();
+        assert!(!value.clone_is_cheap());
+        assert!(!other.clone_is_cheap());
+    }
+
+    #[test]
+    fn replace() {
+        let mut value = Alc::new(String::from("first"));
+        assert_eq!(value.replace(String::from("second")), "first");
+
+        let other = value.lazy();
+        assert_eq!(value.replace(String::from("third")), "second");
+        assert_eq!(value.read(), "third");
+        assert_eq!(other.read(), "second");
+        assert!(other.is_mutable());
+    }
+
+    #[test]
+    fn hash_set_lookup() {
+        let mut set = HashSet::new();
+        set.insert(Alc::new(String::from("key")));
+        set.insert(Alc::new(String::from("other")));
+
+        assert!(set.contains("key"));
+        assert!(set.get("other").is_some_and(|value| value.read() == "other"));
+        assert!(!set.contains("missing"));
+        assert!(set.contains(&Alc::new(String::from("key"))));
+    }
+
+    #[test]
+    fn cow() {
+        let value = Alc::new(String::from("cow"));
+
+        match value.as_cow() {
+            Cow::Borrowed(borrowed) => assert_eq!(borrowed.as_ptr(), value.read().as_ptr()),
+            Cow::Owned(_) => panic!("as_cow must borrow"),
+        }
+
+        let ptr = value.read().as_ptr();
+        match value.into_cow() {
+            Cow::Owned(owned) => assert_eq!(owned.as_ptr(), ptr),
+            Cow::Borrowed(_) => panic!("into_cow must own"),
+        }
+    }
+
+    #[test]
+    fn deref_mut_cow() {
+        let mut value = Alc::new(vec![1, 2, 3]);
+        let other = value.lazy();
+
+        value.push(4);
+
+        assert!(!value.ptr_eq(&other));
+        assert_eq!(other.read(), &vec![1, 2, 3]);
+
+        let another = value.lazy();
+        value.cow_deref_mut().push(5);
+
+        assert!(!value.ptr_eq(&another));
+        assert_eq!(value.read(), &vec![1, 2, 3, 4, 5]);
+        assert_eq!(another.read(), &vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn scoped_mut() {
+        let mut value = Alc::new(vec![1, 2, 3]);
+        let other = value.lazy();
+
+        let len = value.scoped_mut(|vec| {
+            vec.push(4);
+            vec.len()
+        });
+
+        assert_eq!(len, 4);
+        assert_eq!(value.read(), &vec![1, 2, 3, 4]);
+        assert_eq!(other.read(), &vec![1, 2, 3]);
+        assert!(!value.ptr_eq(&other));
+    }
+
+    #[test]
+    fn dedup_handles() {
+        let a = Alc::new(String::from("a"));
+        let b = Alc::new(String::from("b"));
+        let equal_to_a = Alc::new(String::from("a"));
+
+        let mut handles = vec![a.lazy(), b.lazy(), a.lazy(), equal_to_a.lazy(), b.lazy(), a.lazy()];
+        super::dedup_handles(&mut handles);
+
+        assert_eq!(handles.len(), 3);
+        assert!(handles[0].ptr_eq(&a));
+        assert!(handles[1].ptr_eq(&b));
+        assert!(handles[2].ptr_eq(&equal_to_a));
+    }
+
+    #[test]
+    fn from_ref() {
+        let value = String::from("value");
+        let handle = Alc::from_ref(&value);
+
+        assert_eq!(handle.read(), &value);
+        assert_ne!(handle.as_ptr(), value.as_ptr());
+    }
 }
\ No newline at end of file
@@ -1,4 +1,23 @@
+use std::{error::Error, fmt::Display};
+
 pub mod vec;
 pub mod list;
 pub mod avec;
-pub mod alist;
\ No newline at end of file
+pub mod alist;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Error returned when trying to access a collection out of its bounds
+pub struct IndexError {
+    /// The index that was accessed
+    pub index: usize,
+    /// The length of the collection at the moment of the access
+    pub len: usize,
+}
+
+impl Display for IndexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "index out of bounds: the len is {} but the index is {}", self.len, self.index)
+    }
+}
+
+impl Error for IndexError {}
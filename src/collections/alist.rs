@@ -1,6 +1,6 @@
-use std::{collections::LinkedList, mem};
+use std::{collections::{LinkedList, VecDeque}, mem};
 
-use crate::{lazy::LazyClone, alc::Alc};
+use crate::{lazy::LazyClone, alc::Alc, collections::avec::AtomicLazyVec};
 
 #[derive(Debug)]
 /// lazy-cogs implementation of a AtomicLinkedList. Similar to LazyList but thread-safe. 
@@ -229,12 +229,84 @@ impl<T: Clone> AtomicLazyList<T> {
         list.iter().map(Alc::read)
     }
 
+    #[inline(always)]
+    /// Produces an iterator of atomic lazy clones to the elements of the list
+    pub fn iter_lazy(&self) -> impl Iterator<Item = Alc<T>> + '_ {
+        self.list.iter().cloned()
+    }
+
     #[inline(always)]
     /// Produces a mutable iterator over the elements of the list
     pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
         let list = self.list.read_mut();
         list.iter_mut().map(Alc::read_mut)
     }
+
+    #[inline(always)]
+    /// Checks if two AtomicLazyLists are sharing the same underlying storage
+    /// 
+    /// This doesn't compare the contents, so it requires no bound on `T`. 
+    /// Two AtomicLazyLists with equal elements but different allocations aren't `lazy_eq`
+    pub fn lazy_eq(&self, other: &Self) -> bool {
+        self.list.ptr_eq(&other.list)
+    }
+
+    /// Converts the list into an AtomicLazyVec, so its elements can be accessed in O(1)
+    /// 
+    /// The elements are lazily cloned, so the values are shared between the list and the vector. 
+    /// This operation is O(n)
+    pub fn to_lazy_vec(&self) -> AtomicLazyVec<T> {
+        self.list.iter()
+            .cloned()
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    /// Clones every element of the list into a `Vec`, without consuming the list
+    /// 
+    /// This is an actual clone of each value, so it may be expensive
+    pub fn to_vec(&self) -> Vec<T> {
+        self.iter().cloned().collect()
+    }
+
+    /// Returns atomic lazy clones of the handles of the list, in order
+    /// 
+    /// The values are shared between the list and the returned handles, nothing is cloned
+    pub fn as_handles(&self) -> Vec<Alc<T>> {
+        self.iter_lazy().collect()
+    }
+
+    /// Splits the list in two at the given index, returning the elements from `at` to the end
+    /// 
+    /// If `at` is 0 all the elements are moved to the returned list, 
+    /// and if it's greater or equal to the length of the list an empty list is returned. 
+    /// The values are shared between the two lists and the other clones aren't affected
+    pub fn split_off(&mut self, at: usize) -> Self {
+        if at >= self.list.len() {
+            return Self::new();
+        }
+
+        let tail = self.list.read_mut().split_off(at);
+
+        Self {
+            list: Alc::new(tail)
+        }
+    }
+
+    /// Checks if the list contains an element equal to `value`
+    /// 
+    /// This operation is O(n) and never copies the list
+    pub fn contains(&self, value: &T) -> bool
+    where T: PartialEq {
+        self.iter().any(|elem| elem == value)
+    }
+
+    /// Searches for the first element that matches the predicate and returns its index
+    /// 
+    /// This operation is O(n) and never copies the list
+    pub fn position(&self, pred: impl FnMut(&T) -> bool) -> Option<usize> {
+        self.iter().position(pred)
+    }
 }
 
 impl<T: Clone> LazyClone for AtomicLazyList<T> {
@@ -293,6 +365,17 @@ impl<T: Clone> From<Vec<T>> for AtomicLazyList<T> {
     }
 }
 
+impl<T: Clone> From<VecDeque<T>> for AtomicLazyList<T> {
+    fn from(value: VecDeque<T>) -> Self {
+        Self {
+            list: Alc::new(value.into_iter()
+                .map(Alc::new)
+                .collect()
+            ),
+        }
+    }
+}
+
 impl<T: Clone> From<&[T]> for AtomicLazyList<T> {
     fn from(value: &[T]) -> Self {
         value.to_vec()
@@ -318,6 +401,15 @@ impl<T: Clone> Into<LinkedList<T>> for AtomicLazyList<T> {
     }
 }
 
+impl<T: Clone> From<AtomicLazyList<T>> for VecDeque<T> {
+    fn from(value: AtomicLazyList<T>) -> Self {
+        value.list.unwrap()
+            .into_iter()
+            .map(|elem| elem.unwrap())
+            .collect()
+    }
+}
+
 impl<T: Clone> FromIterator<T> for AtomicLazyList<T> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         LinkedList::from_iter(iter).into()
@@ -339,7 +431,7 @@ impl<T: Clone> IntoIterator for AtomicLazyList<T> {
 
 #[cfg(test)]
 mod tests {
-    use std::iter::zip;
+    use std::{collections::VecDeque, iter::zip};
 
     use crate::lazy::LazyClone;
 
@@ -409,4 +501,102 @@ mod tests {
 
         dbg!(lv);
     }
+
+    #[test]
+    fn lazy_eq() {
+        let lv = AtomicLazyList::from(vec![1, 2, 3]);
+        let mut lv2 = lv.lazy();
+
+        assert!(lv.lazy_eq(&lv2));
+        assert!(!lv.lazy_eq(&lv.eager()));
+
+        lv2.push_back(10);
+
+        assert!(!lv.lazy_eq(&lv2));
+    }
+
+    #[test]
+    fn to_lazy_vec() {
+        let list = AtomicLazyList::from(vec![String::from("a"), String::from("b"), String::from("c")]);
+        let vec = list.to_lazy_vec();
+
+        assert!(list.iter().eq(vec.iter()));
+
+        for (i, elem) in list.iter().enumerate() {
+            assert_eq!(elem.as_ptr(), vec.get(i).unwrap().as_ptr());
+        }
+    }
+
+    #[test]
+    fn iter_lazy() {
+        let list = AtomicLazyList::from(vec![String::from("a"), String::from("b"), String::from("c")]);
+
+        for (elem, lazy) in zip(list.iter(), list.iter_lazy()) {
+            assert_eq!(elem.as_ptr(), lazy.as_ptr());
+        }
+
+        assert_eq!(list.iter_lazy().count(), 3);
+    }
+
+    #[test]
+    fn vec_deque() {
+        let mut deque = VecDeque::from(vec![2, 3]);
+        deque.push_front(1);
+
+        let mut list = AtomicLazyList::from(deque.clone());
+        assert!(list.iter().eq(deque.iter()));
+
+        list.push_back(4);
+        let other = list.lazy();
+
+        let deque: VecDeque<_> = list.into();
+        assert_eq!(deque, VecDeque::from(vec![1, 2, 3, 4]));
+        assert!(other.iter().eq(deque.iter()));
+    }
+
+    #[test]
+    fn split_off() {
+        let mut list = AtomicLazyList::from(vec![String::from("a"), String::from("b"), String::from("c")]);
+        let other = list.lazy();
+
+        let tail = list.split_off(1);
+
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec!["a"]);
+        assert_eq!(tail.iter().collect::<Vec<_>>(), vec!["b", "c"]);
+        assert_eq!(other.iter().count(), 3);
+        assert_eq!(tail.get(0).unwrap().as_ptr(), other.get(1).unwrap().as_ptr());
+        assert_eq!(tail.get(1).unwrap().as_ptr(), other.get(2).unwrap().as_ptr());
+
+        assert_eq!(list.split_off(5).iter().count(), 0);
+        assert_eq!(list.split_off(0).iter().collect::<Vec<_>>(), vec!["a"]);
+        assert_eq!(list.iter().count(), 0);
+    }
+
+    #[test]
+    fn search() {
+        let list = AtomicLazyList::from(vec!["rust", "zig", "carbon"]);
+        let other = list.lazy();
+
+        assert!(list.contains(&"zig"));
+        assert!(!list.contains(&"mojo"));
+        assert_eq!(list.position(|elem| elem.starts_with('c')), Some(2));
+        assert_eq!(list.position(|elem| elem.is_empty()), None);
+
+        assert!(list.lazy_eq(&other));
+        assert!(!list.is_mutable());
+    }
+
+    #[test]
+    fn to_vec_and_handles() {
+        let list = AtomicLazyList::from(vec![String::from("a"), String::from("b")]);
+
+        let vec = list.to_vec();
+        let handles = list.as_handles();
+
+        assert_eq!(vec, vec![String::from("a"), String::from("b")]);
+        for ((elem, handle), owned) in zip(zip(list.iter(), &handles), &vec) {
+            assert_eq!(elem.as_ptr(), handle.as_ptr());
+            assert_ne!(elem.as_ptr(), owned.as_ptr());
+        }
+    }
 }
\ No newline at end of file
@@ -1,4 +1,4 @@
-use std::{mem, ops::{Index, IndexMut}};
+use std::{mem, ops::{Index, IndexMut, RangeBounds}};
 
 use crate::{lazy::LazyClone, alc::Alc};
 
@@ -54,6 +54,15 @@ impl<T: Clone> AtomicLazyVec<T> {
         self.vec.get(index).cloned()
     }
 
+    /// Obtains atomic lazy clones to the values in a range of the vector
+    /// 
+    /// If the range is out of bounds it returns `None`
+    pub fn get_lazy_range<R: RangeBounds<usize>>(&self, range: R) -> Option<Vec<Alc<T>>> {
+        self.vec
+            .get((range.start_bound().cloned(), range.end_bound().cloned()))
+            .map(<[Alc<T>]>::to_vec)
+    }
+
     /// Updates an item in the current vector
     /// 
     /// The operation coast dependents on the state of the vector:
@@ -90,6 +99,21 @@ impl<T: Clone> AtomicLazyVec<T> {
         res
     }
 
+    /// Updates an item only if it's still an atomic lazy clone of `expected`
+    /// 
+    /// Returns `Err(())` if the element isn't pointing to the same data as `expected` or if the index is out of range, 
+    /// in those cases nothing is copied. The other clones aren't affected
+    #[allow(clippy::result_unit_err)]
+    pub fn compare_swap_element(&mut self, index: usize, expected: &Alc<T>, new: T) -> Result<(), ()> {
+        match self.vec.get(index) {
+            Some(elem) if elem.ptr_eq(expected) => {
+                self.vec.read_mut()[index].write(new);
+                Ok(())
+            },
+            _ => Err(()),
+        }
+    }
+
     /// Pushes a new element at the end of the vector
     pub fn push(&mut self, value: T) {
         let mut vec = if self.is_mutable() {
@@ -160,6 +184,125 @@ impl<T: Clone> AtomicLazyVec<T> {
         let vec = self.vec.read_mut();
         vec.iter_mut().map(Alc::read_mut)
     }
+
+    #[inline(always)]
+    /// Checks if two AtomicLazyVecs are sharing the same underlying storage
+    /// 
+    /// This doesn't compare the contents, so it requires no bound on `T`. 
+    /// Two AtomicLazyVecs with equal elements but different allocations aren't `lazy_eq`
+    pub fn lazy_eq(&self, other: &Self) -> bool {
+        self.vec.ptr_eq(&other.vec)
+    }
+
+    #[inline(always)]
+    /// Takes an O(1) read-only snapshot of the vector, meant to be handed to readers
+    /// 
+    /// This is a lazy clone, so both the snapshot and this vector become shared. 
+    /// Any later mutation of either of them copies first, so the snapshot never observes changes made to this vector
+    pub fn snapshot(&self) -> Self {
+        self.lazy()
+    }
+
+    #[inline(always)]
+    /// Returns the number of elements the vector can hold without reallocating
+    pub fn capacity(&self) -> usize {
+        self.vec.capacity()
+    }
+
+    /// Removes all the elements of the vector, but keeps its allocated capacity
+    /// 
+    /// If the vector is shared, a new buffer with the same capacity is allocated, so the other clones aren't affected
+    pub fn clear(&mut self) {
+        if self.is_mutable() {
+            self.vec.read_mut().clear();
+        } else {
+            self.vec = Alc::new(Vec::with_capacity(self.capacity()));
+        }
+    }
+
+    #[inline(always)]
+    /// Removes all the elements of the vector and frees its allocated buffer
+    /// 
+    /// The other clones aren't affected
+    pub fn clear_and_free(&mut self) {
+        self.vec = Alc::new(Vec::new());
+    }
+
+    /// Removes all the elements for which the predicate returns `true` and produces an iterator over them
+    /// 
+    /// The elements are removed eagerly, when this method is called, 
+    /// so they're gone even if the iterator is dropped without being fully consumed. 
+    /// The other clones aren't affected
+    pub fn extract_if(&mut self, mut pred: impl FnMut(&T) -> bool) -> impl Iterator<Item = T> {
+        let mut extracted = Vec::new();
+
+        self.vec.read_mut().retain(|elem| {
+            let extract = pred(elem.read());
+            if extract {
+                extracted.push(elem.lazy());
+            }
+
+            !extract
+        });
+
+        extracted.into_iter().map(Alc::unwrap)
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T: Clone + Send + Sync> AtomicLazyVec<T> {
+    /// Produces a fully independent clone of the vector, cloning every value in parallel
+    /// 
+    /// Unlike `eager`, that keeps the values shared and only copies the vector, 
+    /// this doesn't share anything with the original. Useful when `T` is expensive to clone
+    pub fn eager_parallel(&self) -> Self {
+        use rayon::prelude::*;
+
+        Self {
+            vec: Alc::new(self.vec
+                .par_iter()
+                .map(LazyClone::eager)
+                .collect()
+            ),
+        }
+    }
+
+    /// Sorts the vector in parallel, keeping the order of equal elements
+    /// 
+    /// Only the lazy clones are reordered, the values are never cloned. 
+    /// The other clones aren't affected
+    pub fn par_sort(&mut self)
+    where T: Ord {
+        self.par_sort_by(T::cmp)
+    }
+
+    /// Sorts the vector in parallel with a comparator function, keeping the order of equal elements
+    /// 
+    /// Only the lazy clones are reordered, the values are never cloned. 
+    /// The other clones aren't affected
+    pub fn par_sort_by(&mut self, compare: impl Fn(&T, &T) -> std::cmp::Ordering + Sync) {
+        use rayon::prelude::*;
+
+        let vec = self.vec.read_mut();
+
+        vec.par_sort_by(|a, b| compare(a.read(), b.read()));
+    }
+
+    /// Retains only the elements for which the predicate returns `true`, evaluating it in parallel
+    /// 
+    /// The order of the kept elements is preserved and their values are never cloned. 
+    /// The other clones aren't affected
+    pub fn par_retain(&mut self, pred: impl Fn(&T) -> bool + Sync) {
+        use rayon::prelude::*;
+
+        let kept = self.vec
+            .par_iter()
+            .filter(|elem| pred(elem.read()))
+            .cloned()
+            .collect();
+
+        self.vec = Alc::new(kept);
+    }
 }
 
 impl<T: Clone> LazyClone for AtomicLazyVec<T> {
@@ -231,6 +374,34 @@ impl<T: Clone> FromIterator<T> for AtomicLazyVec<T> {
     }
 }
 
+#[cfg(feature = "rayon")]
+impl<T: Clone + Send + Sync> rayon::iter::FromParallelIterator<T> for AtomicLazyVec<T> {
+    fn from_par_iter<I: rayon::iter::IntoParallelIterator<Item = T>>(par_iter: I) -> Self {
+        use rayon::prelude::*;
+
+        Self {
+            vec: Alc::new(par_iter.into_par_iter()
+                .map(Alc::new)
+                .collect()
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T: Clone + Send + Sync> rayon::iter::ParallelExtend<T> for AtomicLazyVec<T> {
+    /// Pushes all the elements of the parallel iterator at the end of the vector
+    /// 
+    /// The other clones aren't affected
+    fn par_extend<I: rayon::iter::IntoParallelIterator<Item = T>>(&mut self, par_iter: I) {
+        use rayon::prelude::*;
+
+        let vec = self.vec.read_mut();
+
+        vec.par_extend(par_iter.into_par_iter().map(Alc::new));
+    }
+}
+
 impl<T: Clone> IntoIterator for AtomicLazyVec<T> {
     type Item = T;
     type IntoIter = std::vec::IntoIter<T>;
@@ -259,7 +430,7 @@ impl<T: Clone> IndexMut<usize> for AtomicLazyVec<T> {
 
 #[cfg(test)]
 mod tests {
-    use std::iter::zip;
+    use std::{iter::zip, panic::{self, AssertUnwindSafe}};
 
     use crate::lazy::LazyClone;
 
@@ -329,4 +500,195 @@ mod tests {
 
         dbg!(lv);
     }
+
+    #[test]
+    fn lazy_eq() {
+        let lv = AtomicLazyVec::from(vec![1, 2, 3]);
+        let mut lv2 = lv.lazy();
+
+        assert!(lv.lazy_eq(&lv2));
+        assert!(!lv.lazy_eq(&lv.eager()));
+
+        let _ = lv2.set(0, 10);
+
+        assert!(!lv.lazy_eq(&lv2));
+    }
+
+    #[test]
+    fn clear() {
+        let mut lv = AtomicLazyVec::from(vec![1, 2, 3, 4]);
+        let capacity = lv.capacity();
+        let lv2 = lv.lazy();
+
+        lv.clear();
+
+        assert_eq!(lv.iter().count(), 0);
+        assert_eq!(lv.capacity(), capacity);
+        assert_eq!(lv2.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+
+        lv.push(5);
+        lv.clear();
+
+        assert_eq!(lv.capacity(), capacity);
+
+        let mut lv3 = lv2.lazy();
+        lv3.clear_and_free();
+
+        assert_eq!(lv3.iter().count(), 0);
+        assert_eq!(lv3.capacity(), 0);
+        assert_eq!(lv2.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn eager_parallel() {
+        let lv = (0..10_000)
+            .map(|i| i.to_string().repeat(8))
+            .collect::<AtomicLazyVec<_>>();
+
+        let sequential = lv.eager();
+        let parallel = lv.eager_parallel();
+
+        assert!(parallel.is_mutable());
+        assert!(parallel.iter().eq(sequential.iter()));
+        assert_ne!(lv.get(0).unwrap().as_ptr(), parallel.get(0).unwrap().as_ptr());
+    }
+
+    #[test]
+    fn get_lazy_range() {
+        let lv = AtomicLazyVec::from(vec![String::from("a"), String::from("b"), String::from("c")]);
+
+        let range = lv.get_lazy_range(1..).unwrap();
+        assert_eq!(range.len(), 2);
+        assert_eq!(range[0].as_ptr(), lv.get(1).unwrap().as_ptr());
+        assert_eq!(range[1].as_ptr(), lv.get(2).unwrap().as_ptr());
+
+        assert_eq!(lv.get_lazy_range(..=2).map(|range| range.len()), Some(3));
+        assert!(lv.get_lazy_range(2..4).is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn par_sort() {
+        let values = (0..100_000u64)
+            .map(|i| i.wrapping_mul(2_654_435_761) % 100_003)
+            .collect::<Vec<_>>();
+
+        let mut lv = AtomicLazyVec::from(values.clone());
+        let lv2 = lv.lazy();
+        let first = lv.get_lazy(0).unwrap();
+
+        lv.par_sort();
+
+        let mut sorted = values.clone();
+        sorted.sort();
+
+        assert!(lv.iter().eq(sorted.iter()));
+        assert!(lv2.iter().eq(values.iter()));
+        assert!(lv.get_lazy_range(..).unwrap().iter().any(|elem| elem.ptr_eq(&first)));
+
+        lv.par_sort_by(|a, b| b.cmp(a));
+        assert!(lv.iter().eq(sorted.iter().rev()));
+    }
+
+    #[test]
+    fn compare_swap_element() {
+        let mut lv = AtomicLazyVec::from(vec![1, 2, 3]);
+        let lv2 = lv.lazy();
+        let expected = lv.get_lazy(1).unwrap();
+
+        assert_eq!(lv.compare_swap_element(1, &expected, 20), Ok(()));
+        assert_eq!(lv.get(1), Some(&20));
+        assert_eq!(lv2.get(1), Some(&2));
+
+        assert_eq!(lv.compare_swap_element(1, &expected, 200), Err(()));
+        assert_eq!(lv.get(1), Some(&20));
+
+        assert_eq!(lv.compare_swap_element(3, &expected, 30), Err(()));
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn parallel_collect() {
+        use rayon::prelude::*;
+
+        let parallel = (0..1_000_000u64)
+            .into_par_iter()
+            .map(|i| i * 3)
+            .collect::<AtomicLazyVec<_>>();
+        let sequential = (0..1_000_000u64)
+            .map(|i| i * 3)
+            .collect::<AtomicLazyVec<_>>();
+
+        assert!(parallel.iter().eq(sequential.iter()));
+
+        let mut extended = parallel.lazy();
+        extended.par_extend((0..10u64).into_par_iter());
+
+        assert_eq!(extended.iter().count(), 1_000_010);
+        assert_eq!(extended.get(1_000_005), Some(&5));
+        assert_eq!(parallel.iter().count(), 1_000_000);
+    }
+
+    #[test]
+    fn extract_if() {
+        let mut lv = AtomicLazyVec::from(vec![1, 2, 3, 4, 5, 6]);
+        let lv2 = lv.lazy();
+
+        let mut extracted = lv.extract_if(|elem| elem % 2 == 0);
+        assert_eq!(extracted.next(), Some(2));
+        drop(extracted);
+
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec![1, 3, 5]);
+        assert_eq!(lv2.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5, 6]);
+
+        assert_eq!(lv.extract_if(|elem| *elem > 1).collect::<Vec<_>>(), vec![3, 5]);
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec![1]);
+    }
+
+    #[test]
+    fn snapshot() {
+        let mut lv = AtomicLazyVec::from(vec![1, 2, 3]);
+        let snapshot = lv.snapshot();
+
+        assert!(lv.lazy_eq(&snapshot));
+        assert!(!lv.is_mutable());
+        assert!(!snapshot.is_mutable());
+
+        lv.push(4);
+        let _ = lv.set(0, 10);
+
+        assert_eq!(snapshot.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec![10, 2, 3, 4]);
+        assert!(snapshot.is_mutable());
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn par_retain() {
+        let values = (0..100_000u64).collect::<Vec<_>>();
+
+        let mut lv = AtomicLazyVec::from(values.clone());
+        let lv2 = lv.lazy();
+
+        lv.par_retain(|elem| elem % 3 == 0);
+
+        let mut expected = values.clone();
+        expected.retain(|elem| elem % 3 == 0);
+
+        assert!(lv.iter().eq(expected.iter()));
+        assert!(lv2.iter().eq(values.iter()));
+    }
+
+    #[test]
+    fn extract_if_panic() {
+        let mut lv = AtomicLazyVec::from(vec![1, 2, 3]);
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            lv.extract_if(|elem| if *elem == 2 { panic!("predicate failed") } else { true }).count()
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec![2, 3]);
+    }
 }
\ No newline at end of file
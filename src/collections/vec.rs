@@ -1,6 +1,6 @@
-use std::{mem, ops::{Index, IndexMut}};
+use std::{cmp::Ordering, collections::HashSet, fmt::Debug, hash::Hash, mem, slice::ChunksExact, ops::{Bound, Deref, DerefMut, Index, IndexMut, Range, RangeBounds}};
 
-use crate::{lazy::LazyClone, lc::Lc};
+use crate::{collections::IndexError, lazy::LazyClone, lc::Lc};
 
 #[derive(Debug)]
 /// lazy-cogs implementation of a Vector. 
@@ -24,6 +24,16 @@ impl<T: Clone> LazyVec<T> {
         }
     }
 
+    /// Creates a LazyVec with `n` copies of `value`, like `vec![value; n]`
+    /// 
+    /// All the elements start as lazy clones of a single value, so it's stored only once. 
+    /// Mutating an element clones the value just for it
+    pub fn from_elem(value: T, n: usize) -> Self {
+        Self {
+            vec: Lc::new(vec![Lc::new(value); n])
+        }
+    }
+
     /// Obtains a reference to a specific value in the lazy vector
     /// 
     /// If the index is out of range it returns `None`
@@ -35,6 +45,13 @@ impl<T: Clone> LazyVec<T> {
         vec.get(index).map(Lc::read)
     }
 
+    /// Obtains a reference to a specific value in the lazy vector
+    /// 
+    /// If the index is out of range it returns an `IndexError` with the index and the length of the vector
+    pub fn try_get(&self, index: usize) -> Result<&T, IndexError> {
+        self.get(index).ok_or(IndexError { index, len: self.vec.len() })
+    }
+
     /// Obtains a mutable reference to a specific value in the lazy vector
     /// 
     /// If the index is out of range it returns `None`
@@ -54,40 +71,150 @@ impl<T: Clone> LazyVec<T> {
         self.vec.get(index).cloned()
     }
 
+    #[inline(always)]
+    /// Obtains an owned copy of a specific value in the lazy vector
+    /// 
+    /// If the index is out of range it returns `None`
+    /// 
+    /// This is an actual clone of the value, so it may be expensive. 
+    /// Use `get` for a reference or `get_lazy` for a shared handle
+    pub fn get_cloned(&self, index: usize) -> Option<T> {
+        self.get(index).cloned()
+    }
+
+    /// Searches for the first element that matches the predicate 
+    /// and returns its index with a lazy clone to it
+    /// 
+    /// Returns `None` if no element matches
+    pub fn find_lazy(&self, mut pred: impl FnMut(&T) -> bool) -> Option<(usize, Lc<T>)> {
+        self.vec.iter()
+            .enumerate()
+            .find(|(_, elem)| pred(elem.read()))
+            .map(|(index, elem)| (index, elem.lazy()))
+    }
+
+    #[inline(always)]
+    /// Copies the lazy clones of the elements into a new `Vec`
+    /// 
+    /// The values are shared with this vector, only the handles are copied
+    pub fn to_handles(&self) -> Vec<Lc<T>> {
+        self.vec.to_vec()
+    }
+
+    /// Searches for the last element that matches the predicate and returns its index
+    /// 
+    /// The vector is scanned from the back and only read, so nothing is cloned. 
+    /// Returns `None` if no element matches
+    pub fn rposition(&self, mut pred: impl FnMut(&T) -> bool) -> Option<usize> {
+        self.vec.iter().rposition(|elem| pred(elem.read()))
+    }
+
+    #[inline(always)]
+    /// Produces an iterator of lazy clones to the elements, from the last to the first
+    pub fn iter_lazy_rev(&self) -> impl Iterator<Item = Lc<T>> + '_ {
+        self.vec.iter().rev().cloned()
+    }
+
     /// Updates an item in the current vector
     /// 
-    /// The operation coast dependents on the state of the vector:
-    /// - If vector was never modified, this costs O(n)
-    /// - If it was modified but some one cloned it, it's also O(n)
-    /// - If it was modified and no one cloned it, it's O(1)
-    /// - If it isn't cloned from other vector and no one cloned it, it's O(1)
+    /// The operation cost depends on the state of the vector:
+    /// - If the vector is shared with other clones, this costs O(n), since the vector of lazy clones must be copied. 
+    ///   Only the lazy clones are copied, the values themselves are never cloned
+    /// - If no one else is sharing the vector, it's O(1) and it's updated in place
+    /// - If the index is out of range, it's O(1) and nothing is copied
+    /// 
+    /// Since all the elements live in a single shared vector, there's no way to avoid copying it when it's shared, 
+    /// even if only the target element is shared. The old value is never cloned, it's just replaced
     pub fn set(&mut self, index: usize, value: T) -> Result<(), ()>{
-        let mut vec = if self.is_mutable() {
-            // We can modify ourselves with no side effects
-            unsafe { 
-                mem::replace(
-                    &mut self.vec,
-                    Lc::new(Vec::new()))
-                .destroy() 
-            }
+        if index >= self.vec.len() {
+            return Err(());
+        }
 
-        } else {
-            // We need to clone the vector so we don't mess with other clones
-            self.vec.take()
-        };
+        // Copies the lazy clones only if some one else is sharing them
+        self.vec.read_mut()[index].write(value);
+
+        Ok(())
+    }
+
+    /// Updates an item in the current vector, just like `set`
+    /// 
+    /// If the index is out of range it returns an `IndexError` with the index and the length of the vector, 
+    /// in that case the vector isn't modified
+    pub fn try_set(&mut self, index: usize, value: T) -> Result<(), IndexError> {
+        let len = self.vec.len();
+
+        self.set(index, value).map_err(|_| IndexError { index, len })
+    }
 
-        let res = match vec.get_mut(index) {
-            Some(elem) => {
-                elem.write(value);
-                Ok(())
-            },
+    #[allow(clippy::result_unit_err)]
+    /// Updates an item in the current vector, just like `set`, but only if the new value differs from the current one
+    /// 
+    /// Returns `Ok(true)` if the element was changed and `Ok(false)` if it already held an equal value. 
+    /// In the latter case nothing is copied, even if the vector is shared. 
+    /// If the index is out of range it returns `Err(())`
+    pub fn set_if_changed(&mut self, index: usize, value: T) -> Result<bool, ()>
+    where T: PartialEq {
+        match self.get(index) {
             None => Err(()),
-        };
-    
-        // Put the modified vector back in the structure
-        self.vec = Lc::new(vec);
-    
-        res
+            Some(current) if *current == value => Ok(false),
+            Some(_) => self.set(index, value).map(|_| true),
+        }
+    }
+
+    /// Overwrites every element of the vector, in order, with the given values
+    /// 
+    /// If the amount of values doesn't match the length of the vector, 
+    /// nothing is changed and the amount of values given is returned as the error
+    pub fn replace_all<I: IntoIterator<Item = T>>(&mut self, values: I) -> Result<(), usize> {
+        let values = values.into_iter().collect::<Vec<_>>();
+
+        if values.len() != self.vec.len() {
+            return Err(values.len());
+        }
+
+        let vec = self.vec.read_mut();
+
+        for (elem, value) in vec.iter_mut().zip(values) {
+            elem.write(value);
+        }
+
+        Ok(())
+    }
+
+    /// Updates an item in the current vector, growing it if the index is out of range
+    /// 
+    /// The gap between the end of the vector and `index` is filled with lazy clones of `default`, 
+    /// so the default value is stored only once. The other clones aren't affected
+    pub fn set_or_grow(&mut self, index: usize, value: T, default: T) {
+        let vec = self.vec.read_mut();
+
+        if index < vec.len() {
+            vec[index].write(value);
+        } else {
+            vec.resize(index, Lc::new(default));
+            vec.push(Lc::new(value));
+        }
+    }
+
+    /// Updates many items in the current vector, copying it only once
+    /// 
+    /// All the indices are checked before anything is updated, 
+    /// so if any of them is out of range, the vector isn't modified and the first of those indices is returned as the error
+    pub fn set_many<I: IntoIterator<Item = (usize, T)>>(&mut self, updates: I) -> Result<(), usize> {
+        let updates = updates.into_iter().collect::<Vec<_>>();
+        let len = self.vec.len();
+
+        if let Some(&(index, _)) = updates.iter().find(|(index, _)| *index >= len) {
+            return Err(index);
+        }
+
+        let vec = self.vec.read_mut();
+
+        for (index, value) in updates {
+            vec[index].write(value);
+        }
+
+        Ok(())
     }
 
     /// Pushes a new element at the end of the vector
@@ -149,6 +276,37 @@ impl<T: Clone> LazyVec<T> {
         self.vec = Lc::new(vec);
     }
 
+    /// Inserts an element keeping the vector sorted and returns the index where it was placed
+    /// 
+    /// The insertion point is found by a binary search, so the vector is expected to be already sorted.
+    /// If there are elements equal to `value`, it is inserted after all of them
+    pub fn insert_sorted(&mut self, value: T) -> usize 
+    where T: Ord {
+        let index = self.vec.partition_point(|elem| elem.read() <= &value);
+
+        let vec = self.vec.read_mut();
+
+        vec.insert(index, Lc::new(value));
+
+        index
+    }
+
+    /// Inserts many elements at a given position in a vector, shifting the following elements only once
+    /// 
+    /// Inserting at the length of the vector appends the elements at its end
+    /// 
+    /// # Panics
+    /// 
+    /// If `index` is greater than the length of the vector
+    pub fn insert_many<I: IntoIterator<Item = T>>(&mut self, index: usize, items: I) {
+        let len = self.vec.len();
+        assert!(index <= len, "insertion index (is {index}) should be <= len (is {len})");
+
+        let vec = self.vec.read_mut();
+
+        vec.splice(index..index, items.into_iter().map(Lc::new));
+    }
+
     /// Produces an iterator over the elements
     pub fn iter(&self) -> impl Iterator<Item = &T> {
         let vec = self.vec.read();
@@ -156,177 +314,1879 @@ impl<T: Clone> LazyVec<T> {
     }
 
     /// Produces a mutable iterator
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+    /// 
+    /// Each element is only cloned when it's actually mutated through its `ElemMut`, 
+    /// so the untouched elements remain shared with the other clones
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = ElemMut<'_, T>> {
         let vec = self.vec.read_mut();
-        vec.iter_mut().map(Lc::read_mut)
+        vec.iter_mut().map(|elem| ElemMut { elem })
     }
-}
 
-impl<T: Clone> LazyClone for LazyVec<T> {
     #[inline(always)]
-    fn lazy(&self) -> Self {
-        Self { 
-            vec: self.vec.lazy()
-        }
+    /// Checks if two LazyVecs are sharing the same underlying storage
+    /// 
+    /// This doesn't compare the contents, so it requires no bound on `T`. 
+    /// Two LazyVecs with equal elements but different allocations aren't `lazy_eq`
+    pub fn lazy_eq(&self, other: &Self) -> bool {
+        self.vec.ptr_eq(&other.vec)
     }
-    
+
     #[inline(always)]
-    fn eager(&self) -> Self {
-        Self { 
-            vec: self.vec.eager()
+    /// Returns the number of elements the vector can hold without reallocating
+    pub fn capacity(&self) -> usize {
+        self.vec.capacity()
+    }
+
+    /// Removes all the elements of the vector, but keeps its allocated capacity
+    /// 
+    /// If the vector is shared, a new buffer with the same capacity is allocated, so the other clones aren't affected
+    pub fn clear(&mut self) {
+        if self.is_mutable() {
+            self.vec.read_mut().clear();
+        } else {
+            self.vec = Lc::new(Vec::with_capacity(self.capacity()));
         }
     }
 
     #[inline(always)]
-    fn is_mutable(&self) -> bool {
-        self.vec.is_mutable()
+    /// Removes all the elements of the vector and frees its allocated buffer
+    /// 
+    /// The other clones aren't affected
+    pub fn clear_and_free(&mut self) {
+        self.vec = Lc::new(Vec::new());
     }
-}
 
-impl<T: Clone> From<Vec<Lc<T>>> for LazyVec<T> {
-    fn from(value: Vec<Lc<T>>) -> Self {
-        Self { 
-            vec: Lc::new(value) 
-        }
+    /// Applies many modifications to the vector at once, taking a private copy of it only once
+    /// 
+    /// The closure receives the inner vector of lazy clones and can change it freely. 
+    /// Since the elements may still be shared with other clones, 
+    /// it's up to the closure to only mutate them through `Lc::read_mut` or `Lc::write`
+    pub fn edit(&mut self, f: impl FnOnce(&mut Vec<Lc<T>>)) {
+        f(self.vec.read_mut());
     }
-}
 
-impl<T: Clone> From<Vec<T>> for LazyVec<T> {
-    fn from(value: Vec<T>) -> Self {
-        Self {
-            vec: Lc::new(value.into_iter()
-                .map(Lc::new)
-                .collect()
-            ),
-        }
-    }
-}
+    /// Removes consecutive elements that are lazy clones of the same value
+    /// 
+    /// Only the pointers are compared, so it doesn't require `T: PartialEq`. 
+    /// Equal values in different allocations are kept
+    pub fn dedup_ptr(&mut self) {
+        let vec = self.vec.read_mut();
 
-impl<T: Clone> From<&[T]> for LazyVec<T> {
-    fn from(value: &[T]) -> Self {
-        value.to_vec().into()
+        vec.dedup_by(|a, b| a.ptr_eq(b));
     }
-}
 
-impl<T: Clone> Into<Vec<Lc<T>>> for LazyVec<T> {
-    fn into(self) -> Vec<Lc<T>> {
-        self.vec.unwrap()
-            .into_iter()
-            .collect()
+    /// Splits the vector in two at the given index, leaving the vector untouched
+    /// 
+    /// The first vector contains the elements in `[0, mid)` and the second the ones in `[mid, len)`. 
+    /// Both of them are lazy clones of the original elements
+    /// 
+    /// # Panics
+    /// 
+    /// If `mid` is greater than the length of the vector
+    pub fn split_at(&self, mid: usize) -> (Self, Self) {
+        let (left, right) = self.vec.split_at(mid);
+
+        (left.to_vec().into(), right.to_vec().into())
     }
-}
 
-impl<T: Clone> Into<Vec<T>> for LazyVec<T> {
-    fn into(self) -> Vec<T> {
-        self.vec.unwrap()
-            .into_iter()
-            .map(|elem| elem.unwrap())
+    /// Splits the vector into `n` contiguous vectors of roughly the same length
+    /// 
+    /// The lengths differ by at most one, the longer ones coming first. 
+    /// If the vector has less than `n` elements, fewer vectors are returned, none of them empty. 
+    /// All of them are lazy clones of the original elements
+    /// 
+    /// # Panics
+    /// 
+    /// If `n` is zero
+    pub fn split_into(&self, n: usize) -> Vec<Self> {
+        assert!(n > 0, "cannot split a LazyVec into zero parts");
+
+        let len = self.vec.len();
+        let (size, extra) = (len / n, len % n);
+        let mut start = 0;
+
+        (0..n.min(len))
+            .map(|i| {
+                let end = start + size + (i < extra) as usize;
+                let part = self.vec[start..end].to_vec().into();
+                start = end;
+                part
+            })
             .collect()
     }
-}
 
-impl<T: Clone> FromIterator<T> for LazyVec<T> {
-    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
-        Vec::from_iter(iter).into()
+    /// Retains only the elements for which the predicate returns `true`
+    /// 
+    /// The predicate receives the index of each element in the vector before any removal, and a reference to it. 
+    /// The other clones aren't affected
+    pub fn retain_indexed(&mut self, mut f: impl FnMut(usize, &T) -> bool) {
+        let vec = self.vec.read_mut();
+
+        let mut index = 0;
+        vec.retain(|elem| {
+            let keep = f(index, elem.read());
+            index += 1;
+            keep
+        });
     }
-}
 
-impl<T: Clone> IntoIterator for LazyVec<T> {
-    type Item = T;
-    type IntoIter = std::vec::IntoIter<T>;
+    /// Retains only the elements for which the predicate returns `true`, letting the predicate mutate them
+    /// 
+    /// The predicate receives the index of each element in the vector before any removal, and a mutable reference to it. 
+    /// Every visited element is cloned first if it's shared, so the other clones aren't affected, 
+    /// even by mutations done to elements that end up removed
+    pub fn retain_mut_indexed(&mut self, mut f: impl FnMut(usize, &mut T) -> bool) {
+        let vec = self.vec.read_mut();
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.vec.take()
-            .into_iter()
-            .map(Lc::unwrap)
-            .collect::<Vec<T>>().into_iter()
+        let mut index = 0;
+        vec.retain_mut(|elem| {
+            let keep = f(index, elem.read_mut());
+            index += 1;
+            keep
+        });
     }
-}
 
-impl<T: Clone> Index<usize> for LazyVec<T> {
-    type Output = T;
+    /// Takes a private copy of the vector (only if it's shared) and returns a guard 
+    /// that gives mutable access to the inner vector of lazy clones
+    /// 
+    /// The modified vector is put back when the guard is dropped. 
+    /// As in `edit`, it's up to the caller to only mutate the elements through `Lc::read_mut` or `Lc::write`
+    pub fn cow_guard(&mut self) -> CoWGuard<'_, T> {
+        let vec = mem::take(self.vec.read_mut());
 
-    fn index(&self, index: usize) -> &Self::Output {
-        self.get(index).unwrap()
+        CoWGuard { owner: self, vec }
     }
-}
 
-impl<T: Clone> IndexMut<usize> for LazyVec<T> {
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        self.get_mut(index).unwrap()
+    /// Produces an iterator over runs of elements where each pair of adjacent elements satisfies the predicate
+    /// 
+    /// Each run is a new LazyVec holding lazy clones of the original elements, 
+    /// so the values are shared with this vector
+    pub fn chunk_by<'a>(&'a self, mut pred: impl FnMut(&T, &T) -> bool + 'a) -> impl Iterator<Item = LazyVec<T>> + 'a {
+        self.vec
+            .chunk_by(move |a, b| pred(a.read(), b.read()))
+            .map(|chunk| chunk.to_vec().into())
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use std::iter::zip;
 
-    use crate::lazy::LazyClone;
+    /// Produces a new vector by applying `f` to each pair of elements of this and the other vector
+    /// 
+    /// Stops at the end of the shorter vector. Neither of the vectors is modified
+    pub fn zip_with<U: Clone, V: Clone>(&self, other: &LazyVec<U>, f: impl Fn(&T, &U) -> V) -> LazyVec<V> {
+        self.iter()
+            .zip(other.iter())
+            .map(|(a, b)| f(a, b))
+            .collect()
+    }
 
-    use super::LazyVec;
+    /// Produces a new vector by applying the fallible `f` to each element
+    /// 
+    /// Stops at the first error and returns it, without building the rest of the vector. 
+    /// This vector isn't modified
+    pub fn try_map<U: Clone, E>(&self, f: impl FnMut(&T) -> Result<U, E>) -> Result<LazyVec<U>, E> {
+        self.iter()
+            .map(f)
+            .collect::<Result<Vec<_>, _>>()
+            .map(LazyVec::from)
+    }
 
-    #[test]
-    fn create() {
-        let mut lv = LazyVec::<Box<str>>::from(&["Hello".into(), "World".into(), "Take".into(), "a look at this".into()] as &[Box<str>]);
-        let mut lv2 = lv.lazy();
-        let mut lv3 = lv2.lazy();
+    /// Retains only the elements in `range` for which the predicate returns `true`, 
+    /// all the elements out of the range are kept
+    /// 
+    /// The other clones aren't affected
+    /// 
+    /// # Panics
+    /// 
+    /// If the range is out of the bounds of the vector or if its start is greater than its end
+    pub fn retain_range<R: RangeBounds<usize>>(&mut self, range: R, mut pred: impl FnMut(&T) -> bool) {
+        let Range { start, end } = to_range(range, self.vec.len());
 
-        let _ = lv.set(0, "Bye".into());
-        let _ = lv2.set(2, "Give".into());
-        let _ = lv3.set(1, "Värld".into());
+        let vec = self.vec.read_mut();
 
-        // Check if the boxes are the same, so we are safely reusing memory
-        assert_eq!(lv.get(1).unwrap().as_ref() as *const str, lv2.get(1).unwrap().as_ref() as *const str);
-        // Check if the boxes are not the same, so we cloned what was needed
-        assert_ne!(lv.get(1).unwrap().as_ref() as *const str, lv3.get(1).unwrap().as_ref() as *const str);
+        let mut index = 0;
+        vec.retain(|elem| {
+            let keep = index < start || index >= end || pred(elem.read());
+            index += 1;
+            keep
+        });
     }
 
-    #[test]
-    #[allow(unused_mut)]
-    #[allow(unused_results)]
-    #[allow(unused_must_use)]
-    fn mutability_check() {
-        let mut lv = LazyVec::from(vec!["HI", "Goodbye", "Farwell", "Hello"]);
-        let mut lv2 = lv.lazy();
-        let mut lv3 = lv2.lazy();
-        let mut lv4 = lv2.lazy();
-        
-        lv2.set(3, "Halo");
-        lv.set(0, "Hej");
+    #[inline(always)]
+    /// Returns a mutable slice of the lazy clones held by the vector, copying them first if the vector is shared
+    /// 
+    /// Useful to reorder the elements in place. It's the mutable counterpart of `as_ref`. 
+    /// Since the elements may still be shared with other clones, 
+    /// it's up to the caller to only mutate them through `Lc::read_mut` or `Lc::write`
+    pub fn as_mut_slice_lazy(&mut self) -> &mut [Lc<T>] {
+        self.vec.read_mut()
+    }
 
-        assert!(lv.is_mutable());
-        assert!(lv2.is_mutable());
-        assert!(!lv3.is_mutable());
-        assert!(!lv4.is_mutable());
+    /// Keeps only the last `keep` elements of the vector, removing the ones at its beginning
+    /// 
+    /// If `keep` is greater or equal to the length of the vector, nothing happens. 
+    /// The other clones aren't affected
+    pub fn truncate_front(&mut self, keep: usize) {
+        let len = self.vec.len();
 
-        for e in zip(zip(lv.iter(), lv2.iter()), zip(lv3.iter(), lv4.iter())) {
-            println!("{:?} : {:?} : {:?} : {:?}", e.0.0.as_ptr(), e.0.1.as_ptr(), e.1.0.as_ptr(), e.1.1.as_ptr())
+        if keep >= len {
+            return;
         }
+
+        let vec = self.vec.read_mut();
+
+        vec.drain(..len - keep);
     }
 
-    #[test]
-    fn iterators() {
-        let lv = LazyVec::from([String::from("rust"), String::from("mojo"), String::from("zig"), String::from("carbon"),String::from("aura")].to_vec());
+    /// Counts how many elements are still sharing their value with some other clone
+    /// 
+    /// If the whole vector is shared, all of its elements are counted. 
+    /// Otherwise only the elements that have living lazy clones are. Useful to inspect how much memory is being reused
+    pub fn count_shared(&self) -> usize {
+        if !self.is_mutable() {
+            return self.vec.len();
+        }
 
-        lv.iter()
-            .map(|elem| elem.to_uppercase())
-            .for_each(|elem| println!("{:?}", elem));
+        self.vec.iter()
+            .filter(|elem| !elem.is_mutable())
+            .count()
+    }
 
-        lv.into_iter()
-            .map(|elem| elem
-                .chars()
-                .rev()
-                .collect::<String>()
-            )
-            .for_each(|elem| println!("{:?}", elem));
+    /// Removes consecutive elements that resolve to the same key
+    /// 
+    /// The other clones aren't affected
+    pub fn dedup_by_key<K: PartialEq>(&mut self, mut key: impl FnMut(&T) -> K) {
+        let vec = self.vec.read_mut();
+
+        vec.dedup_by_key(|elem| key(elem.read()));
     }
 
-    #[test]
-    fn collecting() {
+    /// Appends lazy clones of all the elements of `other` at the end of the vector
+    /// 
+    /// The values are shared between both vectors, so nothing is deep cloned. 
+    /// The other clones of this vector aren't affected
+    pub fn extend_from_lazy(&mut self, other: &LazyVec<T>) {
+        let vec = self.vec.read_mut();
+
+        vec.extend_from_slice(other.vec.read());
+    }
+
+    /// Moves all the elements out of the vector, leaving it empty
+    /// 
+    /// Each value is moved out if no one else is sharing it, otherwise it's cloned. 
+    /// The other clones aren't affected
+    pub fn take_all(&mut self) -> Vec<T> {
+        mem::replace(&mut self.vec, Lc::new(Vec::new()))
+            .unwrap()
+            .into_iter()
+            .map(Lc::unwrap)
+            .collect()
+    }
+
+    /// Retains only the elements for which the predicate returns `true`, without shifting the remaining ones
+    /// 
+    /// Each removed element is replaced by the last one, so the order of the elements **isn't** preserved. 
+    /// The other clones aren't affected
+    pub fn retain_swap(&mut self, mut pred: impl FnMut(&T) -> bool) {
+        let vec = self.vec.read_mut();
+
+        let mut index = 0;
+        while index < vec.len() {
+            if pred(vec[index].read()) {
+                index += 1;
+            } else {
+                vec.swap_remove(index);
+            }
+        }
+    }
+
+    /// Sorts the vector by a key, computing the key of each element only once
+    /// 
+    /// Only the lazy clones are reordered, the values are never cloned. 
+    /// The other clones aren't affected
+    pub fn sort_by_cached_key<K: Ord>(&mut self, mut f: impl FnMut(&T) -> K) {
+        let vec = self.vec.read_mut();
+
+        vec.sort_by_cached_key(|elem| f(elem.read()));
+    }
+
+    /// Splits the vector in two, the first with the elements for which the predicate returns `true` 
+    /// and the second with the rest
+    /// 
+    /// The lazy clones of the elements are moved, so the values are shared with any other clone of this vector
+    pub fn partition(self, mut pred: impl FnMut(&T) -> bool) -> (Self, Self) {
+        let vec: Vec<Lc<T>> = self.into();
+
+        let (matching, rest): (Vec<_>, Vec<_>) = vec.into_iter()
+            .partition(|elem| pred(elem.read()));
+
+        (matching.into(), rest.into())
+    }
+
+    /// Removes all the duplicated elements, wherever they are, keeping only their first occurrence
+    /// 
+    /// The other clones aren't affected
+    pub fn dedup_total(&mut self)
+    where T: Eq + Hash {
+        let vec = self.vec.read_mut();
+
+        let mut seen = HashSet::with_capacity(vec.len());
+        vec.retain(|elem| seen.insert(elem.lazy()));
+    }
+
+    /// Retains only the elements for which the predicate returns `true`, removing the duplicated ones in the same pass
+    /// 
+    /// Among equal elements that match the predicate, only the first occurrence is kept. 
+    /// The predicate is called once for every element, duplicates included. 
+    /// The other clones aren't affected
+    pub fn retain_dedup(&mut self, mut pred: impl FnMut(&T) -> bool)
+    where T: Eq + Hash {
+        let vec = self.vec.read_mut();
+
+        let mut seen = HashSet::with_capacity(vec.len());
+        vec.retain(|elem| pred(elem.read()) && seen.insert(elem.lazy()));
+    }
+
+    /// Clones all the values into a contiguous `Vec<T>`, useful to pass the data through FFI
+    /// 
+    /// This is a deep clone, every value is cloned and nothing is shared with the vector
+    pub fn to_contiguous(&self) -> Vec<T> {
+        self.iter()
+            .cloned()
+            .collect()
+    }
+
+    #[inline(always)]
+    /// Returns a raw pointer to the contiguous buffer of lazy clones held by the vector
+    /// 
+    /// The pointer points to `Lc<T>`s, not to the values, and it's invalidated by any mutation of the vector
+    pub fn as_spine_ptr(&self) -> *const Lc<T> {
+        self.vec.as_ptr()
+    }
+
+    #[inline(always)]
+    /// Returns a reference to the minimum element, or `None` if the vector is empty
+    /// 
+    /// If there are many minimum elements, the first one is returned
+    pub fn min(&self) -> Option<&T>
+    where T: Ord {
+        self.iter().min()
+    }
+
+    #[inline(always)]
+    /// Returns a reference to the maximum element, or `None` if the vector is empty
+    /// 
+    /// If there are many maximum elements, the last one is returned
+    pub fn max(&self) -> Option<&T>
+    where T: Ord {
+        self.iter().max()
+    }
+
+    /// Returns a reference to the minimum element according to the comparator, or `None` if the vector is empty
+    pub fn min_by(&self, mut compare: impl FnMut(&T, &T) -> Ordering) -> Option<&T> {
+        self.iter().min_by(|a, b| compare(a, b))
+    }
+
+    /// Returns a reference to the maximum element according to the comparator, or `None` if the vector is empty
+    pub fn max_by(&self, mut compare: impl FnMut(&T, &T) -> Ordering) -> Option<&T> {
+        self.iter().max_by(|a, b| compare(a, b))
+    }
+
+    #[inline(always)]
+    /// Produces an iterator over chunks of `size` lazy clones, starting from the end of the vector
+    /// 
+    /// If the length isn't a multiple of `size`, the last chunk produced is the shorter one. 
+    /// Nothing is copied
+    /// 
+    /// # Panics
+    /// 
+    /// If `size` is 0
+    pub fn rchunks(&self, size: usize) -> impl Iterator<Item = &[Lc<T>]> {
+        self.vec.rchunks(size)
+    }
+
+    /// Swaps the element at index `i` of this vector with the element at index `j` of `other`
+    /// 
+    /// Only the lazy clones are swapped, the values are never cloned. 
+    /// The other clones of both vectors aren't affected
+    /// 
+    /// # Panics
+    /// 
+    /// If `i` or `j` are out of the bounds of their vectors
+    pub fn swap_elements_between(&mut self, other: &mut LazyVec<T>, i: usize, j: usize) {
+        assert!(i < self.vec.len(), "index {i} is out of range for a vector of length {}", self.vec.len());
+        assert!(j < other.vec.len(), "index {j} is out of range for a vector of length {}", other.vec.len());
+
+        mem::swap(&mut self.vec.read_mut()[i], &mut other.vec.read_mut()[j]);
+    }
+
+    #[inline(always)]
+    /// Checks if the next `push` can be done in place, without copying the vector nor reallocating it
+    /// 
+    /// It's the case when no one else is sharing the vector and it still has spare capacity
+    pub fn can_grow_in_place(&self) -> bool {
+        self.is_mutable() && self.vec.len() < self.vec.capacity()
+    }
+
+    /// Produces an iterator over the elements that are still shared with some other clone, 
+    /// stopping at the first one that isn't
+    /// 
+    /// Just like in `count_shared`, if the whole vector is shared, all of its elements are produced. 
+    /// Useful to inspect the copy-on-write behavior
+    pub fn iter_shared_prefix(&self) -> impl Iterator<Item = &T> {
+        let shared = !self.is_mutable();
+
+        self.vec.iter()
+            .take_while(move |elem| shared || !elem.is_mutable())
+            .map(Lc::read)
+    }
+
+    /// Retains only the first `n` elements for which the predicate returns `true`
+    /// 
+    /// The predicate isn't called anymore after `n` elements were kept. 
+    /// The other clones aren't affected
+    pub fn retain_first_n(&mut self, n: usize, mut pred: impl FnMut(&T) -> bool) {
+        let vec = self.vec.read_mut();
+
+        let mut kept = 0;
+        vec.retain(|elem| {
+            let keep = kept < n && pred(elem.read());
+            kept += keep as usize;
+            keep
+        });
+    }
+
+    /// Checks if the elements of the vector are sorted in ascending order
+    /// 
+    /// Only reads the elements, so nothing is cloned
+    pub fn is_sorted(&self) -> bool
+    where T: Ord {
+        self.is_sorted_by(|a, b| a <= b)
+    }
+
+    /// Checks if the elements of the vector are sorted according to `compare`
+    /// 
+    /// `compare` must return `true` if its two arguments are in order. 
+    /// Only reads the elements, so nothing is cloned
+    pub fn is_sorted_by(&self, mut compare: impl FnMut(&T, &T) -> bool) -> bool {
+        self.vec.windows(2).all(|pair| compare(pair[0].read(), pair[1].read()))
+    }
+
+    /// Folds every element into an accumulator, from the first to the last
+    /// 
+    /// Only reads the elements, so nothing is cloned
+    pub fn fold<B>(&self, init: B, f: impl FnMut(B, &T) -> B) -> B {
+        self.iter().fold(init, f)
+    }
+
+    /// Reduces the elements to a single one by repeatedly applying `f`, or `None` if the vector is empty
+    /// 
+    /// `f` gets references to the accumulated value and to the next element, 
+    /// so only the first element is cloned to start the accumulator
+    pub fn reduce(&self, mut f: impl FnMut(&T, &T) -> T) -> Option<T> {
+        let mut iter = self.iter();
+        let first = iter.next()?.clone();
+
+        Some(iter.fold(first, |acc, elem| f(&acc, elem)))
+    }
+
+    /// Moves the element at `index` to the front of the vector, shifting the preceding elements by one
+    /// 
+    /// The element's lazy clone is moved, so its value is never cloned. 
+    /// The other clones aren't affected
+    /// 
+    /// # Panics
+    /// 
+    /// If `index` is out of bounds
+    pub fn move_to_front(&mut self, index: usize) {
+        assert!(index < self.vec.len(), "index {index} is out of range for a vector of length {}", self.vec.len());
+
+        let vec = self.vec.read_mut();
+
+        vec[..=index].rotate_right(1);
+    }
+
+    /// Applies every edit of the patch to the vector, in order
+    /// 
+    /// The edits are applied sequentially, so each index refers to the vector 
+    /// as left by the previous edits, not to the original one. 
+    /// The spine is taken only once, and the other clones see none of the edits
+    /// 
+    /// Every index is checked before anything is changed. If some edit's index is out of bounds 
+    /// at the time it would be applied, an `IndexError` with that index and the length of the vector 
+    /// at that point is returned, and the vector isn't modified at all
+    pub fn apply_patch(&mut self, patch: Patch<T>) -> Result<(), IndexError> {
+        let mut len = self.vec.len();
+        for edit in &patch.edits {
+            let (index, fits) = match *edit {
+                Edit::Insert(index, _) => (index, index <= len),
+                Edit::Remove(index) | Edit::Replace(index, _) => (index, index < len),
+            };
+
+            if !fits {
+                return Err(IndexError { index, len });
+            }
+
+            match edit {
+                Edit::Insert(..) => len += 1,
+                Edit::Remove(_) => len -= 1,
+                Edit::Replace(..) => {},
+            }
+        }
+
+        let vec = self.vec.read_mut();
+
+        for edit in patch.edits {
+            match edit {
+                Edit::Insert(index, value) => vec.insert(index, Lc::new(value)),
+                Edit::Remove(index) => { vec.remove(index); },
+                Edit::Replace(index, value) => vec[index] = Lc::new(value),
+            }
+        }
+
+        Ok(())
+    }
+
+    #[inline(always)]
+    /// Produces an iterator over chunks of exactly `size` lazy clones, starting from the beginning of the vector
+    /// 
+    /// If the length isn't a multiple of `size`, the last elements are left out of the chunks 
+    /// and can be obtained with `remainder` on the returned iterator. 
+    /// Nothing is copied
+    /// 
+    /// # Panics
+    /// 
+    /// If `size` is 0
+    pub fn chunks_exact(&self, size: usize) -> ChunksExact<'_, Lc<T>> {
+        self.vec.chunks_exact(size)
+    }
+
+    /// Produces a new vector by applying `f` to each sliding window of `size` lazy clones
+    /// 
+    /// Only reads the elements, so nothing is cloned. 
+    /// If the vector is shorter than `size`, the result is empty
+    /// 
+    /// # Panics
+    /// 
+    /// If `size` is 0
+    pub fn windows_map<U: Clone>(&self, size: usize, f: impl FnMut(&[Lc<T>]) -> U) -> LazyVec<U> {
+        self.vec.windows(size).map(f).collect()
+    }
+
+    /// Overwrites every element in `range` with lazy clones of `value`, the elements out of the range are kept
+    /// 
+    /// All the overwritten elements share a single allocation of `value` until one of them is mutated. 
+    /// The other clones aren't affected
+    /// 
+    /// # Panics
+    /// 
+    /// If the range is out of the bounds of the vector or if its start is greater than its end, 
+    /// the range is never clamped
+    pub fn fill_range<R: RangeBounds<usize>>(&mut self, range: R, value: T) {
+        let range = to_range(range, self.vec.len());
+
+        let vec = self.vec.read_mut();
+
+        let value = Lc::new(value);
+        vec[range].fill(value);
+    }
+
+    /// Produces the run-length encoding of the vector, as pairs of a value and the length of its run
+    /// 
+    /// Only reads the vector, but the value of each run is cloned into the result
+    pub fn dedup_counts(&self) -> Vec<(T, usize)>
+    where T: PartialEq {
+        self.vec
+            .chunk_by(|a, b| a.read() == b.read())
+            .map(|run| (run[0].read().clone(), run.len()))
+            .collect()
+    }
+
+    /// Produces an iterator over the segments separated by elements matching the predicate, starting from the end
+    /// 
+    /// The separators aren't included in any segment. 
+    /// Each segment is a new LazyVec holding lazy clones of the original elements, 
+    /// so the values are shared with this vector
+    pub fn rsplit<'a>(&'a self, mut pred: impl FnMut(&T) -> bool + 'a) -> impl Iterator<Item = LazyVec<T>> + 'a {
+        self.vec
+            .rsplit(move |elem| pred(elem.read()))
+            .map(|segment| segment.to_vec().into())
+    }
+}
+
+impl<T: Clone> LazyVec<LazyVec<T>> {
+    /// Concatenates all the inner vectors into a single one, consuming them
+    /// 
+    /// The lazy clones of the elements are moved, so no value is cloned
+    pub fn flatten(self) -> LazyVec<T> {
+        let vec: Vec<Lc<LazyVec<T>>> = self.into();
+
+        vec.into_iter()
+            .flat_map(|inner| Into::<Vec<Lc<T>>>::into(inner.unwrap()))
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    /// Concatenates all the inner vectors into a new one, leaving them untouched
+    /// 
+    /// The new vector holds lazy clones of the elements, so the values are shared with the inner vectors
+    pub fn flatten_lazy(&self) -> LazyVec<T> {
+        self.iter()
+            .flat_map(|inner| inner.vec.iter().cloned())
+            .collect::<Vec<_>>()
+            .into()
+    }
+}
+
+/// Converts any range of indices into a `Range`, checking if it fits in a collection of length `len`
+/// 
+/// # Panics
+/// 
+/// If the range is out of bounds or if its start is greater than its end
+fn to_range<R: RangeBounds<usize>>(range: R, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_add(1)
+            .expect("range start index is out of range for a vector, it overflows usize"),
+        Bound::Unbounded => 0,
+    };
+
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.checked_add(1)
+            .expect("range end index is out of range for a vector, it overflows usize"),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+
+    assert!(start <= end, "range start index {start} is greater than the end index {end}");
+    assert!(end <= len, "range end index {end} is out of range for a vector of length {len}");
+
+    start..end
+}
+
+/// A mutable access to an element of a `LazyVec` produced by `LazyVec::iter_mut`
+/// 
+/// Reading through it never clones the element, it's only cloned at the first mutable dereference, 
+/// and only if it's shared with some other clone
+pub struct ElemMut<'a, T: Clone> {
+    elem: &'a mut Lc<T>,
+}
+
+impl<T: Clone> Deref for ElemMut<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.elem.read()
+    }
+}
+
+impl<T: Clone> DerefMut for ElemMut<'_, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.elem.read_mut()
+    }
+}
+
+impl<T: Clone + Debug> Debug for ElemMut<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ElemMut")
+            .field(self.elem.read())
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A single edit of a `Patch`
+pub enum Edit<T> {
+    /// Inserts the value at the index, shifting the following elements
+    Insert(usize, T),
+    /// Removes the element at the index, shifting the following elements
+    Remove(usize),
+    /// Replaces the element at the index by the value
+    Replace(usize, T),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A list of edits to be applied at once to a `LazyVec` with `LazyVec::apply_patch`
+pub struct Patch<T> {
+    edits: Vec<Edit<T>>,
+}
+
+impl<T> Patch<T> {
+    /// Creates a new empty Patch
+    pub fn new() -> Self {
+        Self { edits: Vec::new() }
+    }
+
+    /// Adds an edit at the end of the patch
+    pub fn push(&mut self, edit: Edit<T>) {
+        self.edits.push(edit);
+    }
+}
+
+impl<T> Default for Patch<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> From<Vec<Edit<T>>> for Patch<T> {
+    fn from(edits: Vec<Edit<T>>) -> Self {
+        Self { edits }
+    }
+}
+
+/// A guard produced by `LazyVec::cow_guard` that gives mutable access to a private copy of the vector
+/// 
+/// The vector is put back into its `LazyVec` when the guard is dropped
+pub struct CoWGuard<'a, T: Clone> {
+    owner: &'a mut LazyVec<T>,
+    vec: Vec<Lc<T>>,
+}
+
+impl<T: Clone> Deref for CoWGuard<'_, T> {
+    type Target = Vec<Lc<T>>;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.vec
+    }
+}
+
+impl<T: Clone> DerefMut for CoWGuard<'_, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.vec
+    }
+}
+
+impl<T: Clone> Drop for CoWGuard<'_, T> {
+    fn drop(&mut self) {
+        self.owner.vec = Lc::new(mem::take(&mut self.vec));
+    }
+}
+
+impl<T: Clone> LazyClone for LazyVec<T> {
+    #[inline(always)]
+    fn lazy(&self) -> Self {
+        Self { 
+            vec: self.vec.lazy()
+        }
+    }
+    
+    #[inline(always)]
+    fn eager(&self) -> Self {
+        Self { 
+            vec: self.vec.eager()
+        }
+    }
+
+    #[inline(always)]
+    fn is_mutable(&self) -> bool {
+        self.vec.is_mutable()
+    }
+}
+
+impl<T: Clone> Clone for LazyVec<T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        LazyClone::lazy(self)
+    }
+}
+
+impl<T: Clone> From<Vec<Lc<T>>> for LazyVec<T> {
+    fn from(value: Vec<Lc<T>>) -> Self {
+        Self { 
+            vec: Lc::new(value) 
+        }
+    }
+}
+
+impl<T: Clone> From<Vec<T>> for LazyVec<T> {
+    fn from(value: Vec<T>) -> Self {
+        Self {
+            vec: Lc::new(value.into_iter()
+                .map(Lc::new)
+                .collect()
+            ),
+        }
+    }
+}
+
+impl<T: Clone> From<&[T]> for LazyVec<T> {
+    fn from(value: &[T]) -> Self {
+        value.to_vec().into()
+    }
+}
+
+impl<T: Clone> From<&LazyVec<T>> for Vec<Lc<T>> {
+    #[inline(always)]
+    fn from(value: &LazyVec<T>) -> Self {
+        value.to_handles()
+    }
+}
+
+impl<T: Clone> Into<Vec<Lc<T>>> for LazyVec<T> {
+    fn into(self) -> Vec<Lc<T>> {
+        self.vec.unwrap()
+            .into_iter()
+            .collect()
+    }
+}
+
+impl<T: Clone> Into<Vec<T>> for LazyVec<T> {
+    fn into(self) -> Vec<T> {
+        self.vec.unwrap()
+            .into_iter()
+            .map(|elem| elem.unwrap())
+            .collect()
+    }
+}
+
+impl<T: Clone> FromIterator<T> for LazyVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Vec::from_iter(iter).into()
+    }
+}
+
+impl<T: Clone> IntoIterator for LazyVec<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.vec.take()
+            .into_iter()
+            .map(Lc::unwrap)
+            .collect::<Vec<T>>().into_iter()
+    }
+}
+
+impl<T: Clone> AsRef<[Lc<T>]> for LazyVec<T> {
+    /// Gives read access to the lazy clones held by the vector, without copying anything
+    /// 
+    /// The borrow lives as long as the vector isn't mutated
+    fn as_ref(&self) -> &[Lc<T>] {
+        self.vec.read()
+    }
+}
+
+impl<T: Clone> Index<usize> for LazyVec<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+impl<T: Clone> IndexMut<usize> for LazyVec<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{iter::zip, panic::{self, AssertUnwindSafe}};
+
+    use crate::{lazy::LazyClone, lc::Lc};
+
+    use crate::collections::IndexError;
+
+    use super::{Edit, LazyVec, Patch};
+
+    #[test]
+    fn create() {
+        let mut lv = LazyVec::<Box<str>>::from(&["Hello".into(), "World".into(), "Take".into(), "a look at this".into()] as &[Box<str>]);
+        let mut lv2 = lv.lazy();
+        let mut lv3 = lv2.lazy();
+
+        let _ = lv.set(0, "Bye".into());
+        let _ = lv2.set(2, "Give".into());
+        let _ = lv3.set(1, "Värld".into());
+
+        // Check if the boxes are the same, so we are safely reusing memory
+        assert_eq!(lv.get(1).unwrap().as_ref() as *const str, lv2.get(1).unwrap().as_ref() as *const str);
+        // Check if the boxes are not the same, so we cloned what was needed
+        assert_ne!(lv.get(1).unwrap().as_ref() as *const str, lv3.get(1).unwrap().as_ref() as *const str);
+    }
+
+    #[test]
+    #[allow(unused_mut)]
+    #[allow(unused_results)]
+    #[allow(unused_must_use)]
+    fn mutability_check() {
+        let mut lv = LazyVec::from(vec!["HI", "Goodbye", "Farwell", "Hello"]);
+        let mut lv2 = lv.lazy();
+        let mut lv3 = lv2.lazy();
+        let mut lv4 = lv2.lazy();
+        
+        lv2.set(3, "Halo");
+        lv.set(0, "Hej");
+
+        assert!(lv.is_mutable());
+        assert!(lv2.is_mutable());
+        assert!(!lv3.is_mutable());
+        assert!(!lv4.is_mutable());
+
+        for e in zip(zip(lv.iter(), lv2.iter()), zip(lv3.iter(), lv4.iter())) {
+            println!("{:?} : {:?} : {:?} : {:?}", e.0.0.as_ptr(), e.0.1.as_ptr(), e.1.0.as_ptr(), e.1.1.as_ptr())
+        }
+    }
+
+    #[test]
+    fn iterators() {
+        let lv = LazyVec::from([String::from("rust"), String::from("mojo"), String::from("zig"), String::from("carbon"),String::from("aura")].to_vec());
+
+        lv.iter()
+            .map(|elem| elem.to_uppercase())
+            .for_each(|elem| println!("{:?}", elem));
+
+        lv.into_iter()
+            .map(|elem| elem
+                .chars()
+                .rev()
+                .collect::<String>()
+            )
+            .for_each(|elem| println!("{:?}", elem));
+    }
+
+    #[test]
+    fn insert_sorted() {
+        let mut lv = LazyVec::new();
+
+        for (i, e) in [5, 1, 4, 1, 3].into_iter().enumerate() {
+            let index = lv.insert_sorted(e);
+            assert_eq!(lv.get(index), Some(&e), "wrong index at insertion {i}");
+        }
+
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec![1, 1, 3, 4, 5]);
+
+        let lv2 = lv.lazy();
+        assert_eq!(lv.insert_sorted(1), 2);
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec![1, 1, 1, 3, 4, 5]);
+        assert_eq!(lv2.iter().copied().collect::<Vec<_>>(), vec![1, 1, 3, 4, 5]);
+    }
+
+    #[test]
+    fn as_ref_slice() {
+        let lv = LazyVec::from(vec![1, 2, 3, 4, 5]);
+        let lv2 = lv.lazy();
+
+        let chunks = lv.as_ref()
+            .chunks(2)
+            .map(|chunk| chunk.iter().map(|elem| **elem).sum::<i32>())
+            .collect::<Vec<_>>();
+
+        assert_eq!(chunks, vec![3, 7, 5]);
+        assert_eq!(lv.as_ref().first().map(|elem| **elem), Some(1));
+        assert!(lv.as_ref()[0].ptr_eq(&lv2.as_ref()[0]));
+        assert!(!lv.is_mutable());
+    }
+
+    #[test]
+    fn collecting() {
         let v = vec!["Hi", "my", "name", "is", "something"];
         let lv: LazyVec<_> = v.into_iter()
             .collect();
 
-        dbg!(lv);
+        dbg!(lv);
+    }
+
+    #[test]
+    fn lazy_eq() {
+        let lv = LazyVec::from(vec![1, 2, 3]);
+        let mut lv2 = lv.lazy();
+
+        assert!(lv.lazy_eq(&lv2));
+        assert!(!lv.lazy_eq(&lv.eager()));
+
+        let _ = lv2.set(0, 10);
+
+        assert!(!lv.lazy_eq(&lv2));
+    }
+
+    #[test]
+    fn iter_mut_promotes_touched() {
+        let lv = LazyVec::from(vec![String::from("a"), String::from("b"), String::from("c")]);
+        let mut lv2 = lv.lazy();
+
+        for (i, mut elem) in lv2.iter_mut().enumerate() {
+            if i == 0 {
+                elem.push('!');
+            } else {
+                assert!(!elem.is_empty());
+            }
+        }
+
+        assert_eq!(lv.get(0).unwrap(), "a");
+        assert_eq!(lv2.get(0).unwrap(), "a!");
+        assert!(!lv.get_lazy(0).unwrap().ptr_eq(&lv2.get_lazy(0).unwrap()));
+        assert!(lv.get_lazy(1).unwrap().ptr_eq(&lv2.get_lazy(1).unwrap()));
+        assert!(lv.get_lazy(2).unwrap().ptr_eq(&lv2.get_lazy(2).unwrap()));
+    }
+
+    #[test]
+    fn clear() {
+        let mut lv = LazyVec::from(vec![1, 2, 3, 4]);
+        let capacity = lv.capacity();
+        let lv2 = lv.lazy();
+
+        lv.clear();
+
+        assert_eq!(lv.iter().count(), 0);
+        assert_eq!(lv.capacity(), capacity);
+        assert_eq!(lv2.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+
+        lv.push(5);
+        lv.clear();
+
+        assert_eq!(lv.capacity(), capacity);
+
+        let mut lv3 = lv2.lazy();
+        lv3.clear_and_free();
+
+        assert_eq!(lv3.iter().count(), 0);
+        assert_eq!(lv3.capacity(), 0);
+        assert_eq!(lv2.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn edit() {
+        let lv = LazyVec::from((0..100).collect::<Vec<_>>());
+        let mut lv2 = lv.lazy();
+
+        lv2.edit(|vec| {
+            for (i, elem) in vec.iter_mut().enumerate() {
+                if i % 2 == 0 {
+                    elem.write(i * 10);
+                }
+            }
+
+            for i in 100..150 {
+                vec.push(Lc::new(i));
+            }
+        });
+
+        assert!(lv2.is_mutable());
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), (0..100).collect::<Vec<_>>());
+        assert_eq!(lv2.get(2), Some(&20));
+        assert_eq!(lv2.get(3), Some(&3));
+        assert_eq!(lv2.get(149), Some(&149));
+        assert!(lv.get_lazy(3).unwrap().ptr_eq(&lv2.get_lazy(3).unwrap()));
+    }
+
+    #[test]
+    fn dedup_ptr() {
+        let a = Lc::new(1);
+        let b = Lc::new(2);
+        let mut lv = LazyVec::<i32>::from(vec![a.lazy(), a.lazy(), Lc::new(1), b.lazy(), b.lazy(), b.lazy(), a.lazy()]);
+        let lv2 = lv.lazy();
+
+        lv.dedup_ptr();
+
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec![1, 1, 2, 1]);
+        assert!(lv.get_lazy(0).unwrap().ptr_eq(&a));
+        assert!(!lv.get_lazy(1).unwrap().ptr_eq(&a));
+        assert!(lv.get_lazy(2).unwrap().ptr_eq(&b));
+        assert_eq!(lv2.iter().count(), 7);
+    }
+
+    #[test]
+    fn split_at() {
+        let lv = LazyVec::from(vec![String::from("a"), String::from("b"), String::from("c")]);
+        let (left, right) = lv.split_at(1);
+
+        assert_eq!(left.iter().collect::<Vec<_>>(), vec!["a"]);
+        assert_eq!(right.iter().collect::<Vec<_>>(), vec!["b", "c"]);
+        assert_eq!(lv.get(0).unwrap().as_ptr(), left.get(0).unwrap().as_ptr());
+        assert_eq!(lv.get(1).unwrap().as_ptr(), right.get(0).unwrap().as_ptr());
+        assert_eq!(lv.get(2).unwrap().as_ptr(), right.get(1).unwrap().as_ptr());
+    }
+
+    #[test]
+    fn try_get_set() {
+        let mut lv = LazyVec::from(vec![1, 2, 3]);
+        let lv2 = lv.lazy();
+
+        assert_eq!(lv.try_get(1), Ok(&2));
+        assert_eq!(lv.try_get(3), Err(IndexError { index: 3, len: 3 }));
+
+        assert_eq!(lv.try_set(5, 10), Err(IndexError { index: 5, len: 3 }));
+        assert!(lv.lazy_eq(&lv2));
+
+        assert_eq!(lv.try_set(0, 10), Ok(()));
+        assert_eq!(lv.get(0), Some(&10));
+        assert_eq!(lv2.get(0), Some(&1));
+    }
+
+    #[test]
+    fn retain_indexed() {
+        let mut lv = LazyVec::from(vec!["a", "b", "c", "d", "e"]);
+        let lv2 = lv.lazy();
+
+        lv.retain_indexed(|i, _| i % 2 != 0);
+
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec!["b", "d"]);
+        assert_eq!(lv2.iter().copied().collect::<Vec<_>>(), vec!["a", "b", "c", "d", "e"]);
+    }
+
+    #[test]
+    fn insert_many() {
+        let mut lv = LazyVec::from(vec![1, 2, 3]);
+        let lv2 = lv.lazy();
+
+        lv.insert_many(1, [10, 20, 30]);
+
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec![1, 10, 20, 30, 2, 3]);
+        assert_eq!(lv2.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+        lv.insert_many(6, vec![4, 5]);
+
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec![1, 10, 20, 30, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn cow_guard() {
+        let mut lv = LazyVec::from(vec![1, 2, 3]);
+        let lv2 = lv.lazy();
+
+        {
+            let mut guard = lv.cow_guard();
+
+            guard.push(Lc::new(4));
+            guard.swap(0, 1);
+            guard[2].write(30);
+            guard.remove(1);
+        }
+
+        assert!(lv.is_mutable());
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec![2, 30, 4]);
+        assert_eq!(lv2.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert!(lv.get_lazy(0).unwrap().ptr_eq(&lv2.get_lazy(1).unwrap()));
+    }
+
+    #[test]
+    fn find_lazy() {
+        let lv = LazyVec::from(vec![String::from("rust"), String::from("zig"), String::from("carbon")]);
+
+        let (index, elem) = lv.find_lazy(|elem| elem.starts_with('z')).unwrap();
+
+        assert_eq!(index, 1);
+        assert_eq!(elem.as_ptr(), lv.get(1).unwrap().as_ptr());
+        assert!(lv.find_lazy(|elem| elem.is_empty()).is_none());
+    }
+
+    #[test]
+    fn replace_all() {
+        let mut lv = LazyVec::from(vec![1, 2, 3]);
+        let lv2 = lv.lazy();
+
+        assert_eq!(lv.replace_all([10, 20]), Err(2));
+        assert_eq!(lv.replace_all(vec![10, 20, 30, 40]), Err(4));
+        assert!(lv.lazy_eq(&lv2));
+
+        assert_eq!(lv.replace_all((1..=3).map(|i| i * 100)), Ok(()));
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec![100, 200, 300]);
+        assert_eq!(lv2.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn chunk_by() {
+        let lv = LazyVec::from(vec![String::from("a"), String::from("a"), String::from("b"), String::from("c"), String::from("c")]);
+
+        let chunks = lv.chunk_by(|a, b| a == b).collect::<Vec<_>>();
+
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks.iter().map(|chunk| chunk.iter().count()).collect::<Vec<_>>(), vec![2, 1, 2]);
+
+        let mut index = 0;
+        for chunk in chunks.iter() {
+            for elem in chunk.iter() {
+                assert_eq!(elem.as_ptr(), lv.get(index).unwrap().as_ptr());
+                index += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn set_keeps_siblings() {
+        let mut lv = LazyVec::from(vec![String::from("a"), String::from("b"), String::from("c")]);
+        let lv2 = lv.lazy();
+
+        assert_eq!(lv.set(1, String::from("B")), Ok(()));
+        assert_eq!(lv.set(3, String::from("D")), Err(()));
+
+        assert_eq!(lv.iter().collect::<Vec<_>>(), vec!["a", "B", "c"]);
+        assert_eq!(lv2.iter().collect::<Vec<_>>(), vec!["a", "b", "c"]);
+        assert!(lv.get_lazy(0).unwrap().ptr_eq(&lv2.get_lazy(0).unwrap()));
+        assert!(lv.get_lazy(2).unwrap().ptr_eq(&lv2.get_lazy(2).unwrap()));
+
+        let spine = lv.as_ref().as_ptr();
+        assert_eq!(lv.set(0, String::from("A")), Ok(()));
+        assert_eq!(lv.as_ref().as_ptr(), spine);
+        assert_eq!(lv2.get(0).unwrap(), "a");
+    }
+
+    #[test]
+    fn zip_with() {
+        let lv = LazyVec::from(vec![1, 2, 3, 4]);
+        let other = LazyVec::from(vec![10, 20, 30]);
+        let lv2 = lv.lazy();
+
+        let sum = lv.zip_with(&other, |a, b| a + b);
+
+        assert_eq!(sum.iter().copied().collect::<Vec<_>>(), vec![11, 22, 33]);
+        assert!(!lv.is_mutable());
+        assert!(other.is_mutable());
+        assert!(lv.lazy_eq(&lv2));
+    }
+
+    #[test]
+    fn retain_range() {
+        let mut lv = LazyVec::from(vec![1, 2, 3, 4, 5, 6]);
+        let lv2 = lv.lazy();
+
+        lv.retain_range(1..5, |elem| elem % 2 == 0);
+
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec![1, 2, 4, 6]);
+        assert_eq!(lv2.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5, 6]);
+
+        lv.retain_range(..=1, |_| false);
+
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec![4, 6]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn retain_range_out_of_bounds() {
+        let mut lv = LazyVec::from(vec![1, 2, 3]);
+
+        lv.retain_range(1..4, |_| true);
+    }
+
+    #[test]
+    fn as_mut_slice_lazy() {
+        let mut lv = LazyVec::from(vec![3, 1, 2]);
+        let lv2 = lv.lazy();
+
+        lv.as_mut_slice_lazy().sort_by_key(|elem| **elem);
+        lv.as_mut_slice_lazy().swap(0, 2);
+
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec![3, 2, 1]);
+        assert_eq!(lv2.iter().copied().collect::<Vec<_>>(), vec![3, 1, 2]);
+        assert!(lv.get_lazy(0).unwrap().ptr_eq(&lv2.get_lazy(0).unwrap()));
+    }
+
+    #[test]
+    fn truncate_front() {
+        let mut lv = LazyVec::from(vec![1, 2, 3, 4, 5]);
+        let lv2 = lv.lazy();
+
+        lv.truncate_front(10);
+        assert!(lv.lazy_eq(&lv2));
+
+        lv.truncate_front(2);
+
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec![4, 5]);
+        assert_eq!(lv2.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+
+        lv.truncate_front(0);
+        assert_eq!(lv.iter().count(), 0);
+    }
+
+    #[test]
+    fn iter_lazy_rev() {
+        let lv = LazyVec::from(vec![String::from("a"), String::from("b"), String::from("c")]);
+        let reversed = LazyVec::<String>::from(lv.iter_lazy_rev().collect::<Vec<_>>());
+
+        assert_eq!(reversed.iter().collect::<Vec<_>>(), vec!["c", "b", "a"]);
+
+        for (i, elem) in lv.iter_lazy_rev().enumerate() {
+            assert_eq!(elem.as_ptr(), lv.get(2 - i).unwrap().as_ptr());
+        }
+    }
+
+    #[test]
+    fn count_shared() {
+        let mut lv = LazyVec::from(vec![1, 2, 3, 4]);
+        assert_eq!(lv.count_shared(), 0);
+
+        let lv2 = lv.lazy();
+        assert_eq!(lv.count_shared(), 4);
+        assert_eq!(lv2.count_shared(), 4);
+
+        let _ = lv.set(2, 30);
+        assert_eq!(lv.count_shared(), 3);
+        assert!(!lv.get_lazy(2).unwrap().ptr_eq(&lv2.get_lazy(2).unwrap()));
+    }
+
+    #[test]
+    fn dedup_by_key() {
+        let mut lv = LazyVec::from(vec![(1, "a"), (1, "b"), (2, "c"), (1, "d"), (1, "e")]);
+        let lv2 = lv.lazy();
+
+        lv.dedup_by_key(|pair| pair.0);
+
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec![(1, "a"), (2, "c"), (1, "d")]);
+        assert_eq!(lv2.iter().count(), 5);
+    }
+
+    #[test]
+    fn flatten() {
+        let a = LazyVec::from(vec![String::from("a"), String::from("b")]);
+        let b = LazyVec::from(vec![String::from("c")]);
+        let nested = LazyVec::from(vec![a.lazy(), LazyVec::new(), b.lazy()]);
+
+        let flat = nested.flatten_lazy();
+
+        assert_eq!(flat.iter().collect::<Vec<_>>(), vec!["a", "b", "c"]);
+        assert_eq!(flat.get(0).unwrap().as_ptr(), a.get(0).unwrap().as_ptr());
+        assert_eq!(flat.get(1).unwrap().as_ptr(), a.get(1).unwrap().as_ptr());
+        assert_eq!(flat.get(2).unwrap().as_ptr(), b.get(0).unwrap().as_ptr());
+
+        let flat = nested.flatten();
+
+        assert_eq!(flat.iter().collect::<Vec<_>>(), vec!["a", "b", "c"]);
+        assert_eq!(flat.get(0).unwrap().as_ptr(), a.get(0).unwrap().as_ptr());
+        assert_eq!(flat.get(2).unwrap().as_ptr(), b.get(0).unwrap().as_ptr());
+    }
+
+    #[test]
+    fn extend_from_lazy() {
+        let mut lv = LazyVec::from(vec![String::from("a")]);
+        let other = LazyVec::from(vec![String::from("b"), String::from("c")]);
+        let lv2 = lv.lazy();
+
+        lv.extend_from_lazy(&other);
+
+        assert_eq!(lv.iter().collect::<Vec<_>>(), vec!["a", "b", "c"]);
+        assert_eq!(lv2.iter().count(), 1);
+        assert_eq!(lv.get(1).unwrap().as_ptr(), other.get(0).unwrap().as_ptr());
+        assert_eq!(lv.get(2).unwrap().as_ptr(), other.get(1).unwrap().as_ptr());
+    }
+
+    #[test]
+    fn from_elem() {
+        let mut lv = LazyVec::from_elem(String::from("same"), 4);
+
+        assert_eq!(lv.iter().count(), 4);
+        assert!(lv.as_ref().windows(2).all(|pair| pair[0].ptr_eq(&pair[1])));
+
+        lv.get_mut(1).unwrap().push('!');
+
+        assert_eq!(lv.iter().collect::<Vec<_>>(), vec!["same", "same!", "same", "same"]);
+        assert!(!lv.get_lazy(1).unwrap().ptr_eq(&lv.get_lazy(0).unwrap()));
+        assert!(lv.get_lazy(0).unwrap().ptr_eq(&lv.get_lazy(2).unwrap()));
+        assert!(lv.get_lazy(0).unwrap().ptr_eq(&lv.get_lazy(3).unwrap()));
+    }
+
+    #[test]
+    fn take_all() {
+        let mut lv = LazyVec::from(vec![String::from("a"), String::from("b")]);
+        let lv2 = lv.lazy();
+
+        assert_eq!(lv.take_all(), vec!["a", "b"]);
+        assert_eq!(lv.iter().count(), 0);
+        assert!(lv.is_mutable());
+        assert_eq!(lv2.iter().collect::<Vec<_>>(), vec!["a", "b"]);
+
+        let mut lv3 = lv2.eager();
+        let ptr = lv3.get(0).unwrap().as_ptr();
+        drop(lv2);
+
+        assert_eq!(lv3.take_all()[0].as_ptr(), ptr);
+    }
+
+    #[test]
+    fn retain_swap() {
+        let mut lv = LazyVec::from(vec![1, 2, 3, 4, 5, 6, 7]);
+        let lv2 = lv.lazy();
+
+        lv.retain_swap(|elem| elem % 3 != 0);
+
+        let mut kept = lv.iter().copied().collect::<Vec<_>>();
+        kept.sort();
+
+        assert_eq!(kept, vec![1, 2, 4, 5, 7]);
+        assert_eq!(lv2.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn set_or_grow() {
+        let mut lv = LazyVec::new();
+
+        lv.set_or_grow(10, 1, 0);
+
+        assert_eq!(lv.iter().count(), 11);
+        assert_eq!(lv.get(10), Some(&1));
+        assert!(lv.iter().take(10).all(|elem| *elem == 0));
+
+        let lv2 = lv.lazy();
+        lv.set_or_grow(3, 5, 0);
+
+        assert_eq!(lv.get(3), Some(&5));
+        assert_eq!(lv2.get(3), Some(&0));
+        assert_eq!(lv.iter().count(), 11);
+    }
+
+    #[test]
+    fn eager_if_shared() {
+        let lv = LazyVec::from(vec![1, 2, 3]);
+
+        let lazy = lv.eager_if_shared();
+        assert!(lazy.lazy_eq(&lv));
+
+        let eager = lv.eager_if_shared();
+        assert!(!eager.lazy_eq(&lv));
+        assert!(eager.iter().eq(lv.iter()));
+    }
+
+    #[test]
+    fn sort_by_cached_key() {
+        let mut lv = LazyVec::from(vec!["ccc", "a", "dddd", "bb"]);
+        let lv2 = lv.lazy();
+        let mut calls = 0;
+
+        lv.sort_by_cached_key(|elem| {
+            calls += 1;
+            elem.len().to_string()
+        });
+
+        assert_eq!(calls, 4);
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec!["a", "bb", "ccc", "dddd"]);
+        assert_eq!(lv2.iter().copied().collect::<Vec<_>>(), vec!["ccc", "a", "dddd", "bb"]);
+    }
+
+    #[test]
+    fn partition() {
+        let lv = LazyVec::from(vec![String::from("rust"), String::from("zig"), String::from("ruby")]);
+        let lv2 = lv.lazy();
+
+        let (r, rest) = lv.partition(|elem| elem.starts_with('r'));
+
+        assert_eq!(r.iter().collect::<Vec<_>>(), vec!["rust", "ruby"]);
+        assert_eq!(rest.iter().collect::<Vec<_>>(), vec!["zig"]);
+        assert_eq!(r.get(0).unwrap().as_ptr(), lv2.get(0).unwrap().as_ptr());
+        assert_eq!(r.get(1).unwrap().as_ptr(), lv2.get(2).unwrap().as_ptr());
+        assert_eq!(rest.get(0).unwrap().as_ptr(), lv2.get(1).unwrap().as_ptr());
+    }
+
+    #[test]
+    fn dedup_total() {
+        let mut lv = LazyVec::from(vec![3, 1, 3, 2, 1, 4, 2]);
+        let lv2 = lv.lazy();
+
+        lv.dedup_total();
+
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec![3, 1, 2, 4]);
+        assert_eq!(lv2.iter().copied().collect::<Vec<_>>(), vec![3, 1, 3, 2, 1, 4, 2]);
+        assert_eq!(lv.count_shared(), 4);
+    }
+
+    #[test]
+    fn to_contiguous() {
+        let lv = LazyVec::from(vec![String::from("a"), String::from("b")]);
+        let contiguous = lv.to_contiguous();
+
+        assert_eq!(contiguous, vec!["a", "b"]);
+        assert_ne!(contiguous[0].as_ptr(), lv.get(0).unwrap().as_ptr());
+        assert_eq!(lv.as_spine_ptr(), lv.as_ref().as_ptr());
+    }
+
+    #[test]
+    fn min_max() {
+        let lv = LazyVec::from(vec![3, 7, 1, 5]);
+        let lv2 = lv.lazy();
+
+        assert_eq!(lv.min(), Some(&1));
+        assert_eq!(lv.max(), Some(&7));
+        assert_eq!(lv.min_by(|a, b| (a % 3).cmp(&(b % 3))), Some(&3));
+        assert_eq!(lv.max_by(|a, b| (a % 3).cmp(&(b % 3))), Some(&5));
+        assert!(lv.lazy_eq(&lv2));
+
+        let empty = LazyVec::<i32>::new();
+
+        assert_eq!(empty.min(), None);
+        assert_eq!(empty.max(), None);
+        assert_eq!(empty.min_by(i32::cmp), None);
+        assert_eq!(empty.max_by(i32::cmp), None);
+    }
+
+    #[test]
+    fn set_many() {
+        let mut lv = LazyVec::from(vec![0, 1, 2, 3, 4]);
+        let lv2 = lv.lazy();
+
+        assert_eq!(lv.set_many([(0, 10), (7, 70), (2, 20), (9, 90)]), Err(7));
+        assert!(lv.lazy_eq(&lv2));
+
+        assert_eq!(lv.set_many([(0, 10), (2, 20), (4, 40)]), Ok(()));
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec![10, 1, 20, 3, 40]);
+        assert_eq!(lv2.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+        assert!(lv.get_lazy(1).unwrap().ptr_eq(&lv2.get_lazy(1).unwrap()));
+    }
+
+    #[test]
+    fn rchunks() {
+        let lv = LazyVec::from(vec![1, 2, 3, 4, 5]);
+
+        let chunks = lv.rchunks(2)
+            .map(|chunk| chunk.iter().map(|elem| **elem).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        assert_eq!(chunks, vec![vec![4, 5], vec![2, 3], vec![1]]);
+        assert!(lv.is_mutable());
+    }
+
+    #[test]
+    fn swap_elements_between() {
+        let mut lv = LazyVec::from(vec![String::from("a"), String::from("b")]);
+        let mut other = LazyVec::from(vec![String::from("x"), String::from("y"), String::from("z")]);
+        let lv2 = lv.lazy();
+        let a = lv.get(0).unwrap().as_ptr();
+        let z = other.get(2).unwrap().as_ptr();
+
+        lv.swap_elements_between(&mut other, 0, 2);
+
+        assert_eq!(lv.iter().collect::<Vec<_>>(), vec!["z", "b"]);
+        assert_eq!(other.iter().collect::<Vec<_>>(), vec!["x", "y", "a"]);
+        assert_eq!(lv.get(0).unwrap().as_ptr(), z);
+        assert_eq!(other.get(2).unwrap().as_ptr(), a);
+        assert_eq!(lv2.iter().collect::<Vec<_>>(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn can_grow_in_place() {
+        let mut lv = LazyVec::from(Vec::<Lc<i32>>::with_capacity(4));
+        lv.push(1);
+        assert!(lv.can_grow_in_place());
+
+        let lv2 = lv.lazy();
+        assert!(!lv.can_grow_in_place());
+        drop(lv2);
+
+        while lv.iter().count() < lv.capacity() {
+            assert!(lv.can_grow_in_place());
+            lv.push(1);
+        }
+
+        assert!(!lv.can_grow_in_place());
+    }
+
+    #[test]
+    fn iter_shared_prefix() {
+        let mut lv = LazyVec::from(vec![0, 1, 2, 3]);
+        assert_eq!(lv.iter_shared_prefix().count(), 0);
+
+        let lv2 = lv.lazy();
+        assert_eq!(lv.iter_shared_prefix().count(), 4);
+
+        let _ = lv.set(2, 20);
+
+        assert_eq!(lv.iter_shared_prefix().copied().collect::<Vec<_>>(), vec![0, 1]);
+        assert_eq!(lv2.iter_shared_prefix().copied().collect::<Vec<_>>(), vec![0, 1]);
+    }
+
+    #[test]
+    fn retain_first_n() {
+        let mut lv = LazyVec::from(vec![1, 2, 3, 4, 5, 6]);
+        let lv2 = lv.lazy();
+
+        lv.retain_first_n(2, |elem| elem % 2 == 1);
+
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!(lv2.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn is_sorted() {
+        let sorted = LazyVec::from(vec![1, 2, 2, 5]);
+        let unsorted = LazyVec::from(vec![3, 1, 2]);
+        let empty = LazyVec::<i32>::new();
+
+        assert!(sorted.is_sorted());
+        assert!(!unsorted.is_sorted());
+        assert!(empty.is_sorted());
+        assert!(!unsorted.is_sorted_by(|a, b| a >= b));
+        assert!(LazyVec::from(vec![3, 2, 1]).is_sorted_by(|a, b| a >= b));
+    }
+
+    #[test]
+    fn fold_and_reduce() {
+        let lv = LazyVec::from(vec![1, 2, 3, 4]);
+        let lv2 = lv.lazy();
+
+        assert_eq!(lv.fold(0, |acc, elem| acc + elem), 10);
+        assert_eq!(lv.reduce(|a, b| a * b), Some(24));
+        assert_eq!(LazyVec::<i32>::new().reduce(|a, b| a + b), None);
+
+        assert!(lv.lazy_eq(&lv2));
+        assert!(!lv.is_mutable());
+    }
+
+    #[test]
+    fn retain_mut_indexed() {
+        let mut lv = LazyVec::from(vec![10, 20, 30, 40]);
+        let lv2 = lv.lazy();
+
+        lv.retain_mut_indexed(|index, elem| {
+            *elem += 1;
+            index % 2 == 0
+        });
+
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec![11, 31]);
+        assert_eq!(lv2.iter().copied().collect::<Vec<_>>(), vec![10, 20, 30, 40]);
+    }
+
+    #[test]
+    fn split_into() {
+        let lv = LazyVec::from((0..7).map(|i| i.to_string()).collect::<Vec<_>>());
+        let parts = lv.split_into(3);
+
+        assert_eq!(parts.iter().map(|part| part.iter().count()).collect::<Vec<_>>(), vec![3, 2, 2]);
+        for (elem, part_elem) in lv.iter().zip(parts.iter().flat_map(LazyVec::iter)) {
+            assert_eq!(elem.as_ptr(), part_elem.as_ptr());
+        }
+
+        let short = LazyVec::from(vec![1, 2]).split_into(4);
+        assert_eq!(short.len(), 2);
+        assert!(short.iter().all(|part| part.iter().count() == 1));
+        assert!(LazyVec::<i32>::new().split_into(2).is_empty());
+    }
+
+    #[test]
+    fn move_to_front() {
+        let mut lv = LazyVec::from(vec![String::from("a"), String::from("b"), String::from("c")]);
+        let lv2 = lv.lazy();
+        let moved = lv.get(2).unwrap().as_ptr();
+
+        lv.move_to_front(2);
+
+        assert_eq!(lv.iter().collect::<Vec<_>>(), vec!["c", "a", "b"]);
+        assert_eq!(lv2.iter().collect::<Vec<_>>(), vec!["a", "b", "c"]);
+        assert_eq!(lv.get(0).unwrap().as_ptr(), moved);
+        assert_eq!(lv2.get(2).unwrap().as_ptr(), moved);
+    }
+
+    #[test]
+    fn get_cloned() {
+        let lv = LazyVec::from(vec![String::from("a"), String::from("b")]);
+        let owned = lv.get_cloned(1).unwrap();
+
+        assert_eq!(&owned, lv.get(1).unwrap());
+        assert_ne!(owned.as_ptr(), lv.get(1).unwrap().as_ptr());
+        assert_eq!(lv.get_cloned(2), None);
+    }
+
+    #[test]
+    fn apply_patch() {
+        let mut lv = LazyVec::from(vec![1, 2, 3, 4]);
+        let lv2 = lv.lazy();
+
+        let mut patch = Patch::from(vec![Edit::Remove(0), Edit::Insert(1, 10)]);
+        patch.push(Edit::Replace(3, 40));
+        assert_eq!(lv.apply_patch(patch), Ok(()));
+
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec![2, 10, 3, 40]);
+        assert_eq!(lv2.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn apply_patch_out_of_bounds() {
+        let mut lv = LazyVec::from(vec![1, 2, 3]);
+
+        let patch = Patch::from(vec![Edit::Replace(0, 10), Edit::Remove(9)]);
+        assert_eq!(lv.apply_patch(patch), Err(IndexError { index: 9, len: 3 }));
+
+        let patch = Patch::from(vec![Edit::Remove(0), Edit::Insert(3, 4)]);
+        assert_eq!(lv.apply_patch(patch), Err(IndexError { index: 3, len: 2 }));
+
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn chunks_exact() {
+        let lv = LazyVec::from(vec![1, 2, 3, 4, 5, 6, 7]);
+        let mut chunks = lv.chunks_exact(3);
+
+        assert_eq!(chunks.remainder().iter().map(|elem| *elem.read()).collect::<Vec<_>>(), vec![7]);
+        assert_eq!(chunks.next().unwrap().len(), 3);
+        assert_eq!(chunks.next().unwrap().len(), 3);
+        assert!(chunks.next().is_none());
+    }
+
+    #[test]
+    fn windows_map() {
+        let values = vec![1, 4, 2, 8, 5, 7];
+        let lv = LazyVec::from(values.clone());
+
+        let sums = lv.windows_map(3, |window| window.iter().map(|elem| *elem.read()).sum::<i32>());
+        let expected = (0..values.len() - 2)
+            .map(|i| values[i] + values[i + 1] + values[i + 2])
+            .collect::<Vec<_>>();
+
+        assert_eq!(sums.iter().copied().collect::<Vec<_>>(), expected);
+        assert!(lv.windows_map(7, |window| window.len()).iter().next().is_none());
+    }
+
+    #[test]
+    fn set_if_changed() {
+        let mut lv = LazyVec::from(vec![1, 2, 3]);
+        let lv2 = lv.lazy();
+
+        assert_eq!(lv.set_if_changed(1, 2), Ok(false));
+        assert!(lv.lazy_eq(&lv2));
+
+        assert_eq!(lv.set_if_changed(1, 5), Ok(true));
+        assert!(!lv.lazy_eq(&lv2));
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec![1, 5, 3]);
+        assert_eq!(lv2.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+        assert_eq!(lv.set_if_changed(3, 0), Err(()));
+    }
+
+    #[test]
+    fn rposition() {
+        let lv = LazyVec::from(vec![1, 4, 2, 4, 3]);
+        let lv2 = lv.lazy();
+
+        assert_eq!(lv.rposition(|elem| *elem == 4), Some(3));
+        assert_eq!(lv.rposition(|elem| *elem > 10), None);
+        assert!(lv.lazy_eq(&lv2));
+    }
+
+    #[test]
+    fn retain_dedup() {
+        let mut lv = LazyVec::from(vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5]);
+        let lv2 = lv.lazy();
+
+        lv.retain_dedup(|elem| elem % 2 == 1);
+
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec![3, 1, 5, 9]);
+        assert_eq!(lv2.iter().copied().collect::<Vec<_>>(), vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5]);
+    }
+
+    #[test]
+    fn to_handles() {
+        let lv = LazyVec::from(vec![String::from("a"), String::from("b")]);
+        let handles = lv.to_handles();
+        let from_ref = Vec::from(&lv);
+
+        assert_eq!(handles.len(), 2);
+        for ((elem, handle), other) in lv.iter().zip(&handles).zip(&from_ref) {
+            assert_eq!(elem.as_ptr(), handle.as_ptr());
+            assert_eq!(elem.as_ptr(), other.as_ptr());
+        }
+    }
+
+    #[test]
+    fn fill_range() {
+        let mut lv = LazyVec::from(vec![1, 2, 3, 4, 5]);
+        let lv2 = lv.lazy();
+
+        lv.fill_range(1..=3, 0);
+
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec![1, 0, 0, 0, 5]);
+        assert_eq!(lv2.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+        assert!(lv.get_lazy(0).unwrap().ptr_eq(&lv2.get_lazy(0).unwrap()));
+        assert!(lv.get_lazy(4).unwrap().ptr_eq(&lv2.get_lazy(4).unwrap()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn fill_range_out_of_bounds() {
+        LazyVec::from(vec![1, 2, 3]).fill_range(2..5, 0);
+    }
+
+    #[test]
+    fn dedup_counts() {
+        let lv = LazyVec::from(vec!['a', 'a', 'b', 'c', 'c', 'c', 'a']);
+        let lv2 = lv.lazy();
+
+        assert_eq!(lv.dedup_counts(), vec![('a', 2), ('b', 1), ('c', 3), ('a', 1)]);
+        assert!(LazyVec::<char>::new().dedup_counts().is_empty());
+        assert!(lv.lazy_eq(&lv2));
+    }
+
+    #[test]
+    fn rsplit() {
+        let lv = LazyVec::from(["a", "b", "|", "c", "|", "d", "e"].map(String::from).to_vec());
+        let segments = lv.rsplit(|elem| elem == "|").collect::<Vec<_>>();
+
+        assert_eq!(segments.len(), 3);
+        assert_eq!(segments[0].iter().collect::<Vec<_>>(), vec!["d", "e"]);
+        assert_eq!(segments[1].iter().collect::<Vec<_>>(), vec!["c"]);
+        assert_eq!(segments[2].iter().collect::<Vec<_>>(), vec!["a", "b"]);
+        assert_eq!(segments[0].get(0).unwrap().as_ptr(), lv.get(5).unwrap().as_ptr());
+        assert_eq!(segments[2].get(1).unwrap().as_ptr(), lv.get(1).unwrap().as_ptr());
+    }
+
+    #[test]
+    fn try_map() {
+        let lv = LazyVec::from(vec!["1", "2", "x", "4"]);
+        let mut calls = 0;
+
+        let result = lv.try_map(|elem| {
+            calls += 1;
+            elem.parse::<i32>()
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 3);
+
+        let parsed = LazyVec::from(vec!["1", "2"]).try_map(|elem| elem.parse::<i32>()).unwrap();
+        assert_eq!(parsed.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_many_out_of_bounds() {
+        LazyVec::from(vec![1, 2, 3]).insert_many(7, [1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn move_to_front_out_of_bounds() {
+        LazyVec::from(vec![1, 2, 3]).move_to_front(5);
+    }
+
+    #[test]
+    fn elem_mut_debug() {
+        let mut lv = LazyVec::from(vec![1, 2]);
+        let lv2 = lv.lazy();
+
+        let debug = lv.iter_mut().map(|elem| format!("{elem:?}")).collect::<Vec<_>>();
+
+        assert_eq!(debug, vec!["ElemMut(1)", "ElemMut(2)"]);
+        assert!(lv.get_lazy(0).unwrap().ptr_eq(&lv2.get_lazy(0).unwrap()));
+    }
+
+    #[test]
+    fn retain_indexed_panic() {
+        let mut lv = LazyVec::from(vec![1, 2, 3]);
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            lv.retain_indexed(|index, _| if index == 1 { panic!("predicate failed") } else { true });
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn edit_panic() {
+        let mut lv = LazyVec::from(vec![1, 2, 3]);
+        let lv2 = lv.lazy();
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            lv.edit(|_| panic!("edit failed"));
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(lv.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(lv2.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn fill_range_overflow() {
+        LazyVec::from(vec![1, 2, 3]).fill_range(0..=usize::MAX, 0);
     }
 }
\ No newline at end of file
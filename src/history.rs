@@ -0,0 +1,158 @@
+use crate::lazy::LazyClone;
+
+#[derive(Debug)]
+/// An undo/redo history built on top of `LazyClone`
+/// 
+/// Every saved state is a lazy clone, so saving one is O(1) and shares the storage with the saved value.
+/// 
+/// The history is meant to be fed with the state right before each change, with `snapshot`. 
+/// `undo` and `redo` receive the current state, so it can be restored later, and return the state to switch to
+pub struct History<C: LazyClone> {
+    undo: Vec<C>,
+    redo: Vec<C>,
+}
+
+impl<C: LazyClone> History<C> {
+    /// Creates a new empty History
+    pub fn new() -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+        }
+    }
+
+    /// Saves a lazy clone of the current state, so a later `undo` can go back to it
+    /// 
+    /// Any state that was undone is discarded, so it can't be redone anymore
+    pub fn snapshot(&mut self, current: &C) {
+        self.redo.clear();
+        self.undo.push(current.lazy());
+    }
+
+    /// Steps back to the latest saved state and returns it
+    /// 
+    /// A lazy clone of `current` is kept, so a following `redo` can go back to it. 
+    /// Returns `None` if there's no saved state, in which case nothing changes
+    pub fn undo(&mut self, current: &C) -> Option<C> {
+        let state = self.undo.pop()?;
+        self.redo.push(current.lazy());
+
+        Some(state)
+    }
+
+    /// Steps forward to the latest undone state and returns it
+    /// 
+    /// A lazy clone of `current` is kept, so a following `undo` can go back to it. 
+    /// Returns `None` if there's nothing to redo, in which case nothing changes
+    pub fn redo(&mut self, current: &C) -> Option<C> {
+        let state = self.redo.pop()?;
+        self.undo.push(current.lazy());
+
+        Some(state)
+    }
+}
+
+impl<C: LazyClone> Default for History<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{collections::vec::LazyVec, lazy::{LazyClone, LazyCopy}};
+
+    use super::History;
+
+    fn values(lv: &LazyVec<i32>) -> Vec<i32> {
+        lv.iter().copied().collect()
+    }
+
+    #[test]
+    fn undo_redo() {
+        let mut history = History::new();
+        let mut lv = LazyVec::from(vec![1, 2, 3]);
+
+        history.snapshot(&lv);
+        assert!(!lv.is_mutable());
+        assert!(lv.lazy_eq(&history.undo[0]));
+
+        lv.push(4);
+        history.snapshot(&lv);
+        lv.push(5);
+
+        lv = history.undo(&lv).unwrap();
+        assert_eq!(values(&lv), vec![1, 2, 3, 4]);
+
+        lv = history.undo(&lv).unwrap();
+        assert_eq!(values(&lv), vec![1, 2, 3]);
+        assert!(history.undo(&lv).is_none());
+
+        lv = history.redo(&lv).unwrap();
+        assert_eq!(values(&lv), vec![1, 2, 3, 4]);
+
+        history.snapshot(&lv);
+        lv.push(6);
+        assert!(history.redo(&lv).is_none());
+
+        lv = history.undo(&lv).unwrap();
+        assert_eq!(values(&lv), vec![1, 2, 3, 4]);
+        lv = history.redo(&lv).unwrap();
+        assert_eq!(values(&lv), vec![1, 2, 3, 4, 6]);
+    }
+
+    #[test]
+    fn undo_single_snapshot() {
+        let mut history = History::new();
+        let mut lv = LazyVec::from(vec![1, 2, 3]);
+
+        history.snapshot(&lv);
+        lv.push(4);
+
+        let restored = history.undo(&lv).unwrap();
+        assert_eq!(values(&restored), vec![1, 2, 3]);
+        assert_eq!(values(&lv), vec![1, 2, 3, 4]);
+
+        assert!(history.undo(&restored).is_none());
+        assert!(History::<LazyVec<i32>>::new().undo(&lv).is_none());
+    }
+
+    #[test]
+    fn undo_lazy_copy() {
+        let mut history = History::new();
+        let mut value = LazyCopy(1u64);
+
+        for next in 2..=4 {
+            history.snapshot(&value);
+            value = LazyCopy(next);
+        }
+
+        let mut undone = Vec::new();
+        while let Some(state) = history.undo(&value) {
+            value = state;
+            undone.push(value.0);
+        }
+
+        assert_eq!(undone, vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn undo_loop_ends() {
+        let mut history = History::new();
+        let mut lv = LazyVec::from(vec![1]);
+
+        history.snapshot(&lv);
+        lv.push(2);
+        history.snapshot(&lv);
+        lv.push(3);
+
+        let mut steps = 0;
+        while let Some(state) = history.undo(&lv) {
+            lv = state;
+            steps += 1;
+        }
+
+        assert_eq!(steps, 2);
+        assert_eq!(values(&lv), vec![1]);
+    }
+}
\ No newline at end of file
@@ -1,3 +1,5 @@
+use std::ops::{Deref, DerefMut};
+
 /// Trait for creating data that can be lazily cloned.
 /// 
 /// This provides both an interface for lazy cloning when the data is known to not be mutated. 
@@ -12,4 +14,96 @@ pub trait LazyClone {
 
     /// Checks if the structure can be mutated with no side effects
     fn is_mutable(&self) -> bool;
+
+    /// Hints if a lazy clone is the cheap choice over an eager one
+    /// 
+    /// Defaults to `true`
+    fn clone_is_cheap(&self) -> bool {
+        true
+    }
+
+    /// Produces `n` lazy clones at once
+    fn lazy_n(&self, n: usize) -> Vec<Self>
+    where Self: Sized {
+        (0..n).map(|_| self.lazy()).collect()
+    }
+
+    /// Produces an eager clone if the data is already shared, and a lazy clone otherwise
+    fn eager_if_shared(&self) -> Self 
+    where Self: Sized {
+        if self.is_mutable() {
+            self.lazy()
+        } else {
+            self.eager()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+/// A wrapper that makes any `Copy` type usable where a `LazyClone` is expected
+/// 
+/// Copying is already as cheap as a lazy clone, so both `lazy` and `eager` just copy the value, 
+/// and it's always mutable. This avoids wrapping small values in an `Lc` only to satisfy a bound
+pub struct LazyCopy<T: Copy> (pub T);
+
+impl<T: Copy> LazyClone for LazyCopy<T> {
+    #[inline(always)]
+    fn lazy(&self) -> Self {
+        *self
+    }
+
+    #[inline(always)]
+    fn eager(&self) -> Self {
+        *self
+    }
+
+    #[inline(always)]
+    fn is_mutable(&self) -> bool {
+        true
+    }
+}
+
+impl<T: Copy> From<T> for LazyCopy<T> {
+    #[inline(always)]
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: Copy> Deref for LazyCopy<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Copy> DerefMut for LazyCopy<T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LazyClone, LazyCopy};
+
+    fn lazy_pair<C: LazyClone>(value: &C) -> (C, C) {
+        (value.lazy(), value.eager())
+    }
+
+    #[test]
+    fn lazy_copy() {
+        let value = LazyCopy(42u64);
+        let (mut lazy, eager) = lazy_pair(&value);
+
+        *lazy += 1;
+
+        assert_eq!(*lazy, 43);
+        assert_eq!(eager, value);
+        assert!(value.is_mutable());
+        assert!(lazy.is_mutable());
+    }
 }
\ No newline at end of file
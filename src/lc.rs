@@ -1,4 +1,4 @@
-use std::{borrow::{Borrow, BorrowMut}, ops::{Deref, DerefMut}, rc::Rc};
+use std::{any::Any, borrow::{Borrow, BorrowMut, Cow}, cell::{Cell, OnceCell, Ref, RefCell, RefMut}, fmt::Debug, hash::{Hash, Hasher}, mem, ops::{Deref, DerefMut}, rc::Rc};
 
 use crate::lazy::LazyClone;
 
@@ -6,6 +6,12 @@ use crate::lazy::LazyClone;
 /// Lc is a LazyClone wrapper, to provide lazy cloning for any data that doesn't implement `LazyClone` trait
 /// 
 /// If you're able to implement LazyClone for the data that you need, do it, if not, use this wrapper
+/// 
+/// # Copy-on-write through `DerefMut`
+/// 
+/// `Lc` implements `DerefMut` for ergonomics, so calling a `&mut self` method of the value (like `push` on a `Lc<Vec<_>>`) 
+/// works directly. But it goes through `read_mut`, which clones the whole value first if it's shared. 
+/// Use `cow_deref_mut` to make that explicit
 pub struct Lc<T> (Rc<T>);
 
 impl<T: Clone> Lc<T> {
@@ -15,12 +21,26 @@ impl<T: Clone> Lc<T> {
         Self(Rc::new(value))
     }
 
+    #[inline(always)]
+    /// Creates a new Lc from the value produced by `f`
+    pub fn from_fn(f: impl FnOnce() -> T) -> Self {
+        Self::new(f())
+    }
+
     #[inline(always)]
     /// Returns a reference to the lazy cloned value
     pub fn read(&self) -> &T {
         &self.0
     }
 
+    #[inline(always)]
+    /// Creates a new Lc from a clone of the referenced value
+    /// 
+    /// The value is cloned once, so this may be expensive
+    pub fn from_ref(value: &T) -> Self {
+        Self::new(value.clone())
+    }
+
     #[inline(always)]
     /// Ensures that the lazily cloned value is mutable and returns a mutable reference to it
     pub fn read_mut(&mut self) -> &mut T {
@@ -33,6 +53,24 @@ impl<T: Clone> Lc<T> {
         Rc::get_mut(&mut self.0).unwrap()
     }
 
+    #[inline(always)]
+    #[must_use]
+    /// Explicitly named version of `DerefMut`, it's the same as `read_mut`
+    /// 
+    /// The value is cloned first if it's shared, so this may be expensive
+    pub fn cow_deref_mut(&mut self) -> &mut T {
+        self.read_mut()
+    }
+
+    #[inline(always)]
+    /// Runs `f` with a mutable reference to the lazy cloned value and returns its result
+    /// 
+    /// Just like `read_mut`, the value is cloned first if it's shared, 
+    /// so the other clones are never affected
+    pub fn scoped_mut<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> R {
+        f(self.read_mut())
+    }
+
     #[inline(always)]
     /// Replaces the cloned value by another
     /// 
@@ -41,6 +79,15 @@ impl<T: Clone> Lc<T> {
         self.0 = Rc::new(value);
     }
 
+    #[inline(always)]
+    /// Replaces the cloned value by another and returns the old one
+    /// 
+    /// Does not affect any value lazily cloned from this value. 
+    /// If the old value was shared, it's cloned out, otherwise it's moved out in O(1)
+    pub fn replace(&mut self, value: T) -> T {
+        mem::replace(self, Lc::new(value)).unwrap()
+    }
+
     #[inline(always)]
     /// Does an actual clone of the contained value
     /// 
@@ -49,12 +96,51 @@ impl<T: Clone> Lc<T> {
         self.0.as_ref().clone()
     }
 
+    #[inline(always)]
+    /// Does an actual clone of the contained value into `dst`
+    /// 
+    /// Uses `Clone::clone_from`, so `dst`'s resources (like the buffer of a `Vec`) may be reused
+    pub fn clone_inner_into(&self, dst: &mut T) {
+        dst.clone_from(self.read());
+    }
+
+    #[inline(always)]
+    /// Checks if this is the only Lc pointing to its data, so it can be mutated without cloning
+    /// 
+    /// Only strong references (other `Lc`s lazily cloned from this one) are counted, weak references are ignored. 
+    /// This is what `LazyClone::is_mutable` uses
+    pub fn is_unique(&self) -> bool {
+        Rc::strong_count(&self.0) == 1
+    }
+
     #[inline(always)]
     /// Checks if two Lc are pointing to the same data
     pub fn ptr_eq(&self, other: &Self) -> bool {
         Rc::ptr_eq(&self.0, &other.0)
     }
 
+    #[inline(always)]
+    /// Checks if two Lc hold the same value
+    /// 
+    /// Returns `true` right away if both point to the same data, without comparing anything. 
+    /// Otherwise the values are compared
+    /// 
+    /// The identity takes precedence, so two Lc pointing to the same data are the same value 
+    /// even if it isn't equal to itself, like a `NaN` float. Two distinct `NaN`s still aren't
+    pub fn is_same_value(&self, other: &Lc<T>) -> bool
+    where T: PartialEq {
+        self.ptr_eq(other) || self.read() == other.read()
+    }
+
+    #[inline(always)]
+    /// Alias of `is_same_value`, named after its identity-first semantics
+    /// 
+    /// Two Lc pointing to the same data are always equal, even if the value isn't equal to itself, like a `NaN` float
+    pub fn ptr_or_value_eq(&self, other: &Lc<T>) -> bool
+    where T: PartialEq {
+        self.is_same_value(other)
+    }
+
     /// Unwraps the lazy clone and returns the inner data in O(1). 
     /// But it only works if the Lc hasn't been lazily cloned, otherwise it panics
     /// 
@@ -68,6 +154,27 @@ impl<T: Clone> Lc<T> {
         Rc::into_inner(self.0).expect("Destroyed a lazy clone that was being shared, this is invalid.")
     }
 
+    #[inline(always)]
+    /// Safely unwraps the lazy clone and returns the inner data in O(1) if this is the last handle to it
+    /// 
+    /// Returns `None` if the data is still shared, in which case this handle is just dropped
+    pub fn into_inner(self) -> Option<T> {
+        Rc::into_inner(self.0)
+    }
+
+    #[inline(always)]
+    /// Borrows the value as a `Cow`, without cloning it
+    pub fn as_cow(&self) -> Cow<'_, T> {
+        Cow::Borrowed(self.read())
+    }
+
+    #[inline(always)]
+    /// Converts the Lc into an owned `Cow`, the value is only cloned if it's shared (see `unwrap`)
+    pub fn into_cow(self) -> Cow<'static, T>
+    where T: 'static {
+        Cow::Owned(self.unwrap())
+    }
+
     /// Unwraps the lazy clone and returns the inner data in O(1) if the Lc is mutable, otherwise performes an clone.
     pub fn unwrap(self) -> T {
         if self.is_mutable() { 
@@ -88,6 +195,7 @@ impl<T: Clone> Deref for Lc<T> {
 }
 
 impl<T: Clone> DerefMut for Lc<T> {
+    /// Same as `read_mut`, so the value is cloned first if it's shared
     #[inline(always)]
     fn deref_mut(&mut self) -> &mut Self::Target {
         self.read_mut()
@@ -106,9 +214,17 @@ impl<T: Clone> LazyClone for Lc<T> {
     }
 
     #[inline(always)]
+    /// Same as `Lc::is_unique`
     fn is_mutable(&self) -> bool {
-        Rc::strong_count(&self.0) == 1
+        self.is_unique()
     }    
+
+    #[inline(always)]
+    /// Returns `true` while the Lc is unique and `false` once it's shared, 
+    /// since writing to a shared value always pays a full clone of it
+    fn clone_is_cheap(&self) -> bool {
+        self.is_mutable()
+    }
 }
 
 impl<T: Clone> Clone for Lc<T> {
@@ -118,6 +234,24 @@ impl<T: Clone> Clone for Lc<T> {
     }
 }
 
+impl<T: Clone + PartialEq> PartialEq for Lc<T> {
+    /// Compares the values, not the pointers. Use `ptr_eq` for that
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.read() == other.read()
+    }
+}
+
+impl<T: Clone + Eq> Eq for Lc<T> {}
+
+impl<T: Clone + Hash> Hash for Lc<T> {
+    /// Hashes the value, so it's consistent with the value's `Borrow` implementations
+    #[inline(always)]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.read().hash(state)
+    }
+}
+
 impl<T: Clone> From<T> for Lc<T> {
     #[inline(always)]
     fn from(value: T) -> Self {
@@ -132,9 +266,463 @@ impl<T: Clone> Borrow<T> for Lc<T> {
     }
 }
 
+impl Borrow<str> for Lc<String> {
+    #[inline(always)]
+    fn borrow(&self) -> &str {
+        self.read()
+    }
+}
+
 impl<T: Clone> BorrowMut<T> for Lc<T> {
     #[inline(always)]
     fn borrow_mut(&mut self) -> &mut T {
         self.read_mut()
     }
+}
+
+/// A lazily initialized Lc. The value is only built by its initializer the first time it's read
+/// 
+/// Lazy clones of a LazyInit share both the value and the initializer, 
+/// so the initializer runs at most once no matter which clone reads it first
+pub struct LazyInit<T: Clone> {
+    value: Lc<OnceCell<T>>,
+    init: Initializer<T>,
+}
+
+/// The initializer of a LazyInit, shared by all its lazy clones until one of them runs it
+type Initializer<T> = Rc<Cell<Option<Box<dyn FnOnce() -> T>>>>;
+
+impl<T: Clone> LazyInit<T> {
+    /// Creates a new LazyInit that will be initialized by `init`
+    pub fn new(init: impl FnOnce() -> T + 'static) -> Self {
+        Self {
+            value: Lc::new(OnceCell::new()),
+            init: Rc::new(Cell::new(Some(Box::new(init)))),
+        }
+    }
+
+    /// Returns a reference to the value, initializing it if it wasn't yet
+    pub fn read(&self) -> &T {
+        self.value.read().get_or_init(|| {
+            let init = self.init.take()
+                .expect("LazyInit initializer was already taken");
+
+            init()
+        })
+    }
+
+    #[inline(always)]
+    /// Checks if the value was already initialized
+    pub fn is_initialized(&self) -> bool {
+        self.value.read().get().is_some()
+    }
+}
+
+impl<T: Clone> LazyClone for LazyInit<T> {
+    #[inline(always)]
+    fn lazy(&self) -> Self {
+        Self {
+            value: self.value.lazy(),
+            init: Rc::clone(&self.init),
+        }
+    }
+
+    /// Produces an independent LazyInit, the value is initialized first if it wasn't yet
+    fn eager(&self) -> Self {
+        Self {
+            value: Lc::new(OnceCell::from(self.read().clone())),
+            init: Rc::new(Cell::new(None)),
+        }
+    }
+
+    #[inline(always)]
+    fn is_mutable(&self) -> bool {
+        self.value.is_mutable()
+    }
+}
+
+impl<T: Clone> Clone for LazyInit<T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        LazyClone::lazy(self)
+    }
+}
+
+impl<T: Clone> Deref for LazyInit<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.read()
+    }
+}
+
+impl<T: Clone + Debug> Debug for LazyInit<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("LazyInit")
+            .field(self.value.read())
+            .finish()
+    }
+}
+
+/// A type-erased value that can still be cloned, so it can be stored in a `Lc<Box<dyn CloneAny>>`
+/// 
+/// It's implemented for every `Clone` type, so any of them can be boxed as a `dyn CloneAny`
+pub trait CloneAny: Any {
+    /// Clones the value into a new box
+    fn clone_box(&self) -> Box<dyn CloneAny>;
+
+    /// Upcasts the value to `&dyn Any`
+    fn as_any(&self) -> &dyn Any;
+
+    /// Upcasts the value to `&mut dyn Any`
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Any + Clone> CloneAny for T {
+    #[inline(always)]
+    fn clone_box(&self) -> Box<dyn CloneAny> {
+        Box::new(self.clone())
+    }
+
+    #[inline(always)]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    #[inline(always)]
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl Clone for Box<dyn CloneAny> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        (**self).clone_box()
+    }
+}
+
+/// Typed access to type-erased values stored in a `Lc<Box<dyn CloneAny>>`
+pub trait LcAnyExt {
+    /// Returns a reference to the inner value if it's of type `U`, otherwise returns `None`
+    fn downcast_ref<U: 'static>(&self) -> Option<&U>;
+
+    /// Returns a mutable reference to the inner value if it's of type `U`, otherwise returns `None`
+    /// 
+    /// Just like `read_mut`, the value is cloned first if it's shared, but only when the type matches
+    fn downcast_mut<U: 'static>(&mut self) -> Option<&mut U>;
+}
+
+impl LcAnyExt for Lc<Box<dyn CloneAny>> {
+    #[inline(always)]
+    fn downcast_ref<U: 'static>(&self) -> Option<&U> {
+        (**self.read()).as_any().downcast_ref()
+    }
+
+    fn downcast_mut<U: 'static>(&mut self) -> Option<&mut U> {
+        if !(**self.read()).as_any().is::<U>() {
+            return None;
+        }
+
+        (**self.read_mut()).as_any_mut().downcast_mut()
+    }
+}
+
+#[derive(Debug)]
+/// SharedLc is the opt-in counterpart of `Lc` for shared mutable state
+/// 
+/// Cloning a SharedLc never copies the value, and mutations made through any clone are visible to all of them. 
+/// There's no copy-on-write, so it doesn't implement `LazyClone`. 
+/// Use `Lc` whenever each clone should own its changes
+pub struct SharedLc<T> (Rc<RefCell<T>>);
+
+impl<T> SharedLc<T> {
+    #[inline(always)]
+    /// Creates a new SharedLc from a value
+    pub fn new(value: T) -> Self {
+        Self(Rc::new(RefCell::new(value)))
+    }
+
+    #[inline(always)]
+    /// Immutably borrows the shared value
+    /// 
+    /// # Panics
+    /// 
+    /// If the value is currently mutably borrowed through any clone
+    pub fn borrow(&self) -> Ref<'_, T> {
+        (*self.0).borrow()
+    }
+
+    #[inline(always)]
+    /// Mutably borrows the shared value, the changes are seen by every clone
+    /// 
+    /// # Panics
+    /// 
+    /// If the value is currently borrowed through any clone
+    pub fn borrow_mut(&self) -> RefMut<'_, T> {
+        (*self.0).borrow_mut()
+    }
+
+    #[inline(always)]
+    /// Checks if two SharedLc point to the same value
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<T> Clone for SharedLc<T> {
+    #[inline(always)]
+    /// Produces another handle to the same value, nothing is copied
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{borrow::Cow, cell::Cell, collections::HashSet, rc::Rc};
+
+    use crate::lazy::LazyClone;
+
+    use super::{CloneAny, LazyInit, Lc, LcAnyExt, SharedLc};
+
+    #[test]
+    fn into_inner() {
+        let unique = Lc::new(String::from("unique"));
+        assert_eq!(unique.into_inner(), Some(String::from("unique")));
+
+        let shared = Lc::new(String::from("shared"));
+        let other = shared.lazy();
+
+        assert_eq!(shared.into_inner(), None);
+        assert_eq!(other.into_inner(), Some(String::from("shared")));
+    }
+
+    #[test]
+    fn clone_inner_into() {
+        let lc = Lc::new(vec![1u8, 2, 3]);
+        let mut dst = Vec::with_capacity(1024);
+        dst.push(10u8);
+        let ptr = dst.as_ptr();
+
+        lc.clone_inner_into(&mut dst);
+
+        assert_eq!(dst, vec![1, 2, 3]);
+        assert_eq!(dst.as_ptr(), ptr);
+        assert!(dst.capacity() >= 1024);
+    }
+
+    #[test]
+    fn is_unique() {
+        let lc = Lc::new(1);
+        assert!(lc.is_unique());
+
+        let other = lc.lazy();
+        assert!(!lc.is_unique());
+        assert!(!other.is_unique());
+
+        drop(other);
+        assert!(lc.is_unique());
+        assert_eq!(lc.is_unique(), lc.is_mutable());
+    }
+
+    #[test]
+    fn downcast_ref() {
+        let number: Lc<Box<dyn CloneAny>> = Lc::new(Box::new(10i32));
+        let string: Lc<Box<dyn CloneAny>> = Lc::new(Box::new(String::from("ten")));
+
+        assert_eq!(number.downcast_ref::<i32>(), Some(&10));
+        assert_eq!(number.downcast_ref::<String>(), None);
+        assert_eq!(string.downcast_ref::<String>().map(String::as_str), Some("ten"));
+        assert_eq!(string.downcast_ref::<i32>(), None);
+    }
+
+    #[test]
+    fn downcast_mut() {
+        let mut string: Lc<Box<dyn CloneAny>> = Lc::new(Box::new(String::from("ten")));
+        let other = string.lazy();
+
+        assert!(string.downcast_mut::<i32>().is_none());
+        assert!(string.ptr_eq(&other));
+
+        string.downcast_mut::<String>().unwrap().push('!');
+
+        assert!(!string.ptr_eq(&other));
+        assert_eq!(string.downcast_ref::<String>().map(String::as_str), Some("ten!"));
+        assert_eq!(other.downcast_ref::<String>().map(String::as_str), Some("ten"));
+    }
+
+    #[test]
+    fn clone_is_cheap() {
+        let value = Lc::new(vec![1, 2, 3]);
+        assert!(value.clone_is_cheap());
+
+        let other = value.lazy();
+        assert!(!value.clone_is_cheap());
+        assert!(!other.clone_is_cheap());
+    }
+
+    #[test]
+    fn replace() {
+        let mut value = Lc::new(String::from("first"));
+        assert_eq!(value.replace(String::from("second")), "first");
+
+        let other = value.lazy();
+        assert_eq!(value.replace(String::from("third")), "second");
+        assert_eq!(value.read(), "third");
+        assert_eq!(other.read(), "second");
+        assert!(other.is_mutable());
+    }
+
+    #[test]
+    fn from_fn() {
+        let lc = Lc::from_fn(|| vec![1, 2, 3]);
+
+        assert_eq!(lc.read(), &vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn lazy_init() {
+        let calls = Rc::new(Cell::new(0));
+        let counter = Rc::clone(&calls);
+
+        let value = LazyInit::new(move || {
+            counter.set(counter.get() + 1);
+            String::from("initialized")
+        });
+        let other = value.lazy();
+
+        assert!(!value.is_initialized());
+        assert_eq!(calls.get(), 0);
+
+        assert_eq!(other.read(), "initialized");
+        assert_eq!(value.read(), "initialized");
+        assert_eq!(value.lazy().len(), 11);
+
+        let eager = value.eager();
+        assert_eq!(eager.read(), "initialized");
+        assert!(eager.is_mutable());
+
+        assert!(value.is_initialized());
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn hash_set_lookup() {
+        let mut set = HashSet::new();
+        set.insert(Lc::new(String::from("key")));
+        set.insert(Lc::new(String::from("other")));
+
+        assert!(set.contains("key"));
+        assert!(set.get("other").is_some_and(|value| value.read() == "other"));
+        assert!(!set.contains("missing"));
+        assert!(set.contains(&Lc::new(String::from("key"))));
+    }
+
+    #[test]
+    fn is_same_value() {
+        let lc = Lc::new(String::from("value"));
+
+        assert!(lc.is_same_value(&lc.lazy()));
+        assert!(lc.is_same_value(&lc.eager()));
+        assert!(!lc.is_same_value(&Lc::new(String::from("other"))));
+
+        let nan = Lc::new(f64::NAN);
+        assert!(nan.is_same_value(&nan.lazy()));
+        assert!(!nan.is_same_value(&nan.eager()));
+        assert!(nan.ptr_or_value_eq(&nan.lazy()));
+    }
+
+    #[test]
+    fn eager_if_shared() {
+        let lc = Lc::new(1);
+
+        let lazy = lc.eager_if_shared();
+        assert!(lazy.ptr_eq(&lc));
+
+        let eager = lc.eager_if_shared();
+        assert!(!eager.ptr_eq(&lc));
+        assert_eq!(eager.read(), &1);
+    }
+
+    #[test]
+    fn cow() {
+        let value = Lc::new(String::from("cow"));
+
+        match value.as_cow() {
+            Cow::Borrowed(borrowed) => assert_eq!(borrowed.as_ptr(), value.read().as_ptr()),
+            Cow::Owned(_) => panic!("as_cow must borrow"),
+        }
+
+        let ptr = value.read().as_ptr();
+        match value.into_cow() {
+            Cow::Owned(owned) => assert_eq!(owned.as_ptr(), ptr),
+            Cow::Borrowed(_) => panic!("into_cow must own"),
+        }
+    }
+
+    #[test]
+    fn lazy_n() {
+        let lc = Lc::new(String::from("fan-out"));
+        let clones = lc.lazy_n(4);
+
+        assert_eq!(clones.len(), 4);
+        assert_eq!(Rc::strong_count(&lc.0), 5);
+        assert!(clones.iter().all(|clone| clone.ptr_eq(&lc)));
+    }
+
+    #[test]
+    fn deref_mut_cow() {
+        let mut value = Lc::new(vec![1, 2, 3]);
+        let other = value.lazy();
+
+        value.push(4);
+
+        assert!(!value.ptr_eq(&other));
+        assert_eq!(other.read(), &vec![1, 2, 3]);
+
+        let another = value.lazy();
+        value.cow_deref_mut().push(5);
+
+        assert!(!value.ptr_eq(&another));
+        assert_eq!(value.read(), &vec![1, 2, 3, 4, 5]);
+        assert_eq!(another.read(), &vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn scoped_mut() {
+        let mut value = Lc::new(vec![1, 2, 3]);
+        let other = value.lazy();
+
+        let len = value.scoped_mut(|vec| {
+            vec.push(4);
+            vec.len()
+        });
+
+        assert_eq!(len, 4);
+        assert_eq!(value.read(), &vec![1, 2, 3, 4]);
+        assert_eq!(other.read(), &vec![1, 2, 3]);
+        assert!(!value.ptr_eq(&other));
+    }
+
+    #[test]
+    fn from_ref() {
+        let value = String::from("value");
+        let handle = Lc::from_ref(&value);
+
+        assert_eq!(handle.read(), &value);
+        assert_ne!(handle.as_ptr(), value.as_ptr());
+    }
+
+    #[test]
+    fn shared_lc() {
+        let shared = SharedLc::new(vec![1, 2, 3]);
+        let other = shared.clone();
+
+        other.borrow_mut().push(4);
+
+        assert!(shared.ptr_eq(&other));
+        assert_eq!(*shared.borrow(), vec![1, 2, 3, 4]);
+    }
 }
\ No newline at end of file
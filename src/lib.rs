@@ -1,4 +1,7 @@
 pub mod lc;
 pub mod alc;
 pub mod lazy;
-pub mod collections;
\ No newline at end of file
+pub mod collections;
+pub mod history;
+pub mod pool;
+pub mod memo;
\ No newline at end of file
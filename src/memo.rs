@@ -0,0 +1,106 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::{lazy::LazyClone, lc::Lc};
+
+#[derive(Debug)]
+/// A memoization cache whose snapshots are lazy clones
+/// 
+/// Every cached value is stored in its own `Lc`, so looking a value up hands out a shared handle to it. 
+/// Lazily cloning a Memo shares the whole cache until one of the clones computes a new entry
+pub struct Memo<K: Clone + Eq + Hash, V: Clone> {
+    cache: Lc<HashMap<K, Lc<V>>>,
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> Memo<K, V> {
+    /// Creates a new empty Memo
+    pub fn new() -> Self {
+        Self {
+            cache: Lc::new(HashMap::new()),
+        }
+    }
+
+    /// Returns a lazy clone of the value cached for `key`, computing it with `f` if it's missing
+    /// 
+    /// If the value is already cached `f` isn't called and nothing is copied. 
+    /// Otherwise the cache is copied first if it's shared, so the other clones don't see the new entry
+    pub fn get_or_compute(&mut self, key: K, f: impl FnOnce() -> V) -> Lc<V> {
+        if let Some(value) = self.cache.get(&key) {
+            return value.lazy();
+        }
+
+        let value = Lc::new(f());
+        self.cache.read_mut().insert(key, value.lazy());
+
+        value
+    }
+
+    #[inline(always)]
+    /// Returns a lazy clone of the value cached for `key`, if any
+    pub fn get(&self, key: &K) -> Option<Lc<V>> {
+        self.cache.get(key).map(LazyClone::lazy)
+    }
+
+    #[inline(always)]
+    /// Checks if there's a value cached for `key`
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.cache.contains_key(key)
+    }
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> LazyClone for Memo<K, V> {
+    #[inline(always)]
+    fn lazy(&self) -> Self {
+        Self {
+            cache: self.cache.lazy(),
+        }
+    }
+
+    #[inline(always)]
+    fn eager(&self) -> Self {
+        Self {
+            cache: self.cache.eager(),
+        }
+    }
+
+    #[inline(always)]
+    fn is_mutable(&self) -> bool {
+        self.cache.is_mutable()
+    }
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> Clone for Memo<K, V> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        self.lazy()
+    }
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lazy::LazyClone;
+
+    use super::Memo;
+
+    #[test]
+    fn get_or_compute() {
+        let mut memo = Memo::new();
+        let first = memo.get_or_compute(1, || String::from("one"));
+
+        let snapshot = memo.lazy();
+        let again = memo.get_or_compute(1, || unreachable!());
+        assert!(first.ptr_eq(&again));
+        assert!(!memo.is_mutable());
+
+        memo.get_or_compute(2, || String::from("two"));
+
+        assert!(memo.contains_key(&2));
+        assert!(!snapshot.contains_key(&2));
+        assert!(snapshot.get(&1).unwrap().ptr_eq(&first));
+    }
+}
@@ -0,0 +1,53 @@
+use crate::lazy::LazyClone;
+
+#[derive(Debug)]
+/// A pool of objects built from a single template, following the prototype pattern
+/// 
+/// Every acquired object is a lazy clone of the template, so acquiring is O(1) and shares the storage with it. 
+/// The actual clone only happens when an acquired object is mutated
+pub struct LazyPool<T: Clone + LazyClone> {
+    template: T,
+}
+
+impl<T: Clone + LazyClone> LazyPool<T> {
+    /// Creates a new LazyPool that hands out lazy clones of `template`
+    pub fn new(template: T) -> Self {
+        Self { template }
+    }
+
+    #[inline(always)]
+    /// Returns a lazy clone of the template
+    /// 
+    /// This is O(1), the template is only cloned when the returned object is mutated
+    pub fn acquire(&self) -> T {
+        self.template.lazy()
+    }
+
+    #[inline(always)]
+    /// Returns a reference to the template
+    pub fn template(&self) -> &T {
+        &self.template
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collections::vec::LazyVec;
+
+    use super::LazyPool;
+
+    #[test]
+    fn acquire() {
+        let pool = LazyPool::new(LazyVec::from(vec![1, 2, 3]));
+
+        let mut objects = (0..4).map(|_| pool.acquire()).collect::<Vec<_>>();
+        assert!(objects.iter().all(|obj| obj.lazy_eq(pool.template())));
+
+        objects[0].push(4);
+
+        assert!(!objects[0].lazy_eq(pool.template()));
+        assert!(objects[1..].iter().all(|obj| obj.lazy_eq(pool.template())));
+        assert_eq!(objects[0].iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(pool.template().iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+}